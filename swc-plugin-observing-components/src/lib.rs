@@ -1,44 +1,83 @@
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 use swc_core::{
     ecma::ast::Program,
-    common::plugin::metadata::TransformPluginMetadataContextKind,
+    common::{errors::HANDLER, plugin::metadata::TransformPluginMetadataContextKind},
     plugin::{plugin_transform, proxies::TransformPluginProgramMetadata},
 };
 
 #[plugin_transform]
 fn swc_plugin(program: Program, data: TransformPluginProgramMetadata) -> Program {
-    let config = serde_json::from_str::<Option<wrap_components_with_observer::Config>>(
-        &data
-            .get_transform_plugin_config()
-            .expect("failed to get plugin config for observing-components"),
-    )
-    .expect("invalid plugin config")
-    .unwrap();
+    // The configuration is the same for every file, so it is parsed and its
+    // patterns compiled only once
+    let raw_config = data.get_transform_plugin_config().unwrap_or_default();
+    let cached = match wrap_components_with_observer::CachedConfig::new(&raw_config) {
+        Ok(cached) => cached,
+        Err(err) => {
+            // Report through SWC instead of panicking, leaving the file as is
+            HANDLER.with(|handler| handler.err(&err.to_string()));
+            return program;
+        }
+    };
+
+    for warning in cached.take_warnings() {
+        HANDLER.with(|handler| handler.warn(warning));
+    }
 
     // Get the filename from metadata
     let filename = data
         .get_context(&TransformPluginMetadataContextKind::Filename)
         .unwrap_or_default();
+
+    // Patterns are resolved relative to the cwd of the compilation
+    let cwd = data.get_context(&TransformPluginMetadataContextKind::Cwd);
     
-    // Check exclusion patterns
-    let is_excluded = wrap_components_with_observer::should_exclude(&filename, &config.exclude);
+    // Check extension, node_modules, include and exclude patterns, then pick
+    // the options of the first matching rule
+    let config = cached.config_for(&filename, cwd.as_deref());
     
-    // Add debug output for path matching
-    #[cfg(debug_assertions)]
-    {
-        eprintln!("File: {}", filename);
-        eprintln!("Exclude patterns: {:?}", config.exclude);
-        eprintln!("Is excluded: {}", is_excluded);
+    tracing::debug!(
+        file = %filename,
+        cwd = ?cwd,
+        include = ?cached.config.include,
+        exclude = ?cached.config.exclude,
+        include_node_modules = ?cached.config.include_node_modules,
+        import_path = ?config.map(|config| &config.import_path),
+        "matched file against the configuration"
+    );
+
+    let Some(config) = config else {
+        return program;
+    };
+
+    // The options of an `@observing-components` header comment apply to both
+    // passes, and their import paths are resolved like the others
+    let mut config = wrap_components_with_observer::header_config(&program, config, data.comments);
+
+    // Import paths relative to the project root are made relative to the file
+    config.import_path =
+        wrap_components_with_observer::resolve_import_path(&config.import_path, &filename, cwd.as_deref())
+            .into_owned();
+    for wrapper in &mut config.wrappers {
+        wrapper.import_path =
+            wrap_components_with_observer::resolve_import_path(&wrapper.import_path, &filename, cwd.as_deref())
+                .into_owned();
+    }
+    if let Some(import_path) = config.render_props.as_mut().and_then(|render_props| render_props.import_path.as_mut()) {
+        *import_path =
+            wrap_components_with_observer::resolve_import_path(import_path, &filename, cwd.as_deref()).into_owned();
+    }
+
+    if let Some(import_path) = config.actions.as_mut().and_then(|actions| actions.import_path.as_mut()) {
+        *import_path =
+            wrap_components_with_observer::resolve_import_path(import_path, &filename, cwd.as_deref()).into_owned();
     }
-    
-    // Check if we should process this file:
-    // 1. Skip node_modules
-    // 2. Check against exclude patterns
-    let should_process = !filename.contains("node_modules") && !is_excluded;
 
-    if !should_process {
+    // Files targeting another runtime, e.g. Solid, are left alone by both passes
+    if !wrap_components_with_observer::targets_react(&program, &config, data.comments) {
         return program;
     }
 
-    program.apply(wrap_components_with_observer::observer_transform(config))
-}
\ No newline at end of file
+    program
+        .apply(wrap_components_with_observer::action_transform(config.clone(), data.comments))
+        .apply(wrap_components_with_observer::observer_transform(config, &filename, data.comments))
+}
@@ -0,0 +1,57 @@
+//! Compares the per-file cost of preparing the plugin configuration with and
+//! without [CachedConfig]. Run with `cargo bench -p wrap_components_with_observer`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use wrap_components_with_observer::{CachedConfig, Config, FileMatcher};
+
+const FILES: usize = 6_000;
+
+const CONFIG: &str = r#"{
+    "import_path": "mobx-react-lite",
+    "include": ["src/**", "packages/*/src/**"],
+    "exclude": ["**/*.stories.tsx", "**/*.test.tsx", "src/generated/", "!src/generated/keep.tsx"]
+}"#;
+
+fn file_paths() -> Vec<String> {
+    (0..FILES)
+        .map(|i| format!("/repo/packages/package-{}/src/components/Component{}.tsx", i % 40, i))
+        .collect()
+}
+
+fn run(name: &str, files: &[String], prepare: impl Fn(&str) -> bool) -> Duration {
+    let start = Instant::now();
+    for file in files {
+        black_box(prepare(file));
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{:<10} {:>10.2?} total, {:>8} ns/file",
+        name,
+        elapsed,
+        elapsed.as_nanos() / files.len() as u128
+    );
+
+    elapsed
+}
+
+fn main() {
+    let files = file_paths();
+
+    let uncached = run("uncached", &files, |file| {
+        let config = Config::from_json(black_box(CONFIG)).unwrap();
+        FileMatcher::new(&config).unwrap().is_match(file, Some("/repo"))
+    });
+
+    let cached = run("cached", &files, |file| {
+        let config = CachedConfig::new(black_box(CONFIG)).unwrap();
+        config.matcher.is_match(file, Some("/repo"))
+    });
+
+    println!(
+        "speedup    {:>10.1}x",
+        uncached.as_secs_f64() / cached.as_secs_f64()
+    );
+}
@@ -0,0 +1,115 @@
+//! Inline event handlers wrapped in mobx's `action`
+
+use swc_common::comments::Comments;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{visit_mut_pass, VisitMut, VisitMutWith};
+
+use crate::{
+    components::wrapped_components, context::Ctx, create_import, has_import, jsx::JsxPragmas, Config, ImportStyle, Wrapper,
+};
+
+/// Wraps the inline event handlers of components in `action` when `actions`
+/// is configured, e.g. `onClick={action(() => store.count++)}`, so they don't
+/// trigger warnings with mobx's `enforceActions: "observed"`. Runs alongside
+/// [crate::observer_transform], with the same comments, for programs that
+/// [crate::targets_react].
+///
+/// Only the components the observer wraps are changed. Handlers are the arrow
+/// and function expressions passed to `on[A-Z]*` attributes. Handlers passed
+/// by name or wrapped in any call already are left alone.
+pub fn action_transform<C: Comments>(config: Config, comments: C) -> impl Pass {
+    visit_mut_pass(ActionTransform { config, comments })
+}
+
+struct ActionTransform<C: Comments> {
+    config: Config,
+    comments: C,
+}
+
+impl<C: Comments> VisitMut for ActionTransform<C> {
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        let Some(actions) = &self.config.actions else {
+            return;
+        };
+
+        let ctx = Ctx::new(module, &self.config, &JsxPragmas::of_module(module, &self.comments));
+
+        let mut wrapper = HandlerWrapper {
+            action_name: actions.action_name(),
+            has_action: false,
+        };
+
+        for item in module.body.iter_mut() {
+            for (_, mut component) in wrapped_components(&ctx, item) {
+                component.visit_mut_with(&mut wrapper);
+            }
+        }
+
+        let action = Wrapper {
+            import_path: actions.action_path().to_string(),
+            import_name: actions.action_name().to_string(),
+            import_style: ImportStyle::Named,
+            args: Vec::new(),
+        };
+
+        if wrapper.has_action && !has_import(module, &action) {
+            module.body.insert(0, create_import(&action));
+        }
+    }
+}
+
+struct HandlerWrapper<'a> {
+    action_name: &'a str,
+    // Whether a handler was wrapped, so `action` must be imported
+    has_action: bool,
+}
+
+impl VisitMut for HandlerWrapper<'_> {
+    fn visit_mut_jsx_attr(&mut self, attr: &mut JSXAttr) {
+        attr.visit_mut_children_with(self);
+
+        if let Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        })) = &mut attr.value
+        {
+            if is_event_handler(&attr.name) && is_inline_function(expr) {
+                **expr = Expr::Call(CallExpr {
+                    span: Default::default(),
+                    callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+                        self.action_name.into(),
+                        Default::default(),
+                        Default::default(),
+                    )))),
+                    args: vec![ExprOrSpread {
+                        spread: None,
+                        expr: std::mem::replace(expr, Box::new(Expr::Invalid(Default::default()))),
+                    }],
+                    type_args: None,
+                    ctxt: Default::default(),
+                });
+                self.has_action = true;
+            }
+        }
+    }
+}
+
+// Checks for `onClick`, `onChange` etc.
+fn is_event_handler(name: &JSXAttrName) -> bool {
+    let JSXAttrName::Ident(name) = name else {
+        return false;
+    };
+
+    name.sym
+        .strip_prefix("on")
+        .and_then(|event| event.chars().next())
+        .is_some_and(|first| first.is_ascii_uppercase())
+}
+
+fn is_inline_function(expr: &Expr) -> bool {
+    match expr {
+        Expr::Arrow(_) | Expr::Fn(_) => true,
+        Expr::Paren(paren) => is_inline_function(&paren.expr),
+        _ => false,
+    }
+}
@@ -0,0 +1,127 @@
+//! Configuration cache
+
+use std::{
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+};
+
+use crate::{config, Config, ConfigError, FileMatcher};
+
+/// A [Config] together with the matchers compiled from it.
+#[derive(Debug)]
+pub struct CompiledConfig {
+    pub config: Config,
+    pub matcher: FileMatcher,
+    // The matcher and the options of every rule, in order
+    rules: Vec<(FileMatcher, Config)>,
+    warnings: Vec<String>,
+    warnings_reported: AtomicBool,
+}
+
+impl CompiledConfig {
+    /// Returns the options to transform the file with, or `None` when it
+    /// shouldn't be transformed. The first rule matching the file wins, and
+    /// files matching no rule use the top-level options if `import_path` is set.
+    pub fn config_for(&self, file_path: &str, cwd: Option<&str>) -> Option<&Config> {
+        if !self.matcher.is_match(file_path, cwd) {
+            return None;
+        }
+
+        let rule = self
+            .rules
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(file_path, cwd));
+
+        match rule {
+            Some((_, config)) => Some(config),
+            None if self.config.import_path.trim().is_empty() => None,
+            None => Some(&self.config),
+        }
+    }
+
+    /// Returns the warnings about the configuration the first time it is
+    /// called, so they are reported once per build rather than for every file.
+    pub fn take_warnings(&self) -> &[String] {
+        if self.warnings_reported.swap(true, Ordering::Relaxed) {
+            &[]
+        } else {
+            &self.warnings
+        }
+    }
+}
+
+// How many configurations are kept, e.g. for the rules of a watching dev
+// server whose configuration changes
+const CACHE_SIZE: usize = 8;
+
+// The cached configurations by their raw JSON, the most recently used last
+type Cache = Mutex<Vec<(String, Arc<CompiledConfig>)>>;
+
+/// A [CompiledConfig] cached based on the raw JSON it was parsed from.
+///
+/// The plugin is invoked once per file with the same configuration, so this
+/// parses the configuration and compiles its globs only once per build. Only
+/// the most recently used configurations are kept.
+#[derive(Debug, Clone)]
+pub struct CachedConfig {
+    compiled: Arc<CompiledConfig>,
+}
+
+impl Deref for CachedConfig {
+    type Target = CompiledConfig;
+
+    fn deref(&self) -> &Self::Target {
+        &self.compiled
+    }
+}
+
+impl CachedConfig {
+    /// Get or create a cached configuration. This will return the previous
+    /// instance if the same configuration was parsed before.
+    pub fn new(input: &str) -> Result<Self, ConfigError> {
+        // Like `swc_cached::regex::CachedRegex`, which only caches regexes
+        static CACHE: OnceLock<Cache> = OnceLock::new();
+        let cache = CACHE.get_or_init(Default::default);
+
+        {
+            let mut cache = cache.lock().unwrap();
+            if let Some(index) = cache.iter().position(|(raw, _)| raw == input) {
+                let entry = cache.remove(index);
+                let compiled = entry.1.clone();
+                cache.push(entry);
+                return Ok(CachedConfig { compiled });
+            }
+        }
+
+        let (config, warnings) = config::parse(input)?;
+        let matcher = FileMatcher::new(&config)?;
+        let rules = config
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                let config = rule.resolve(&config);
+                let matcher = FileMatcher::new(&config).map_err(|err| err.in_rule(index))?;
+                Ok((matcher, config))
+            })
+            .collect::<Result<_, ConfigError>>()?;
+        let compiled = Arc::new(CompiledConfig {
+            config,
+            matcher,
+            rules,
+            warnings,
+            warnings_reported: AtomicBool::new(false),
+        });
+
+        let mut cache = cache.lock().unwrap();
+        if cache.len() >= CACHE_SIZE {
+            cache.remove(0);
+        }
+        cache.push((input.to_owned(), compiled.clone()));
+
+        Ok(CachedConfig { compiled })
+    }
+}
@@ -0,0 +1,158 @@
+//! Components declared at the top level of a module
+
+use std::collections::HashSet;
+
+use swc_atoms::Atom;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+
+use crate::{
+    context::Ctx, is_component_expr, is_component_function, is_component_name, is_default_component_expr,
+    is_default_component_function, names,
+};
+
+/// The function of a component, or the expression containing it, e.g.
+/// `observer(() => <div />)`
+pub(crate) enum Component<'a> {
+    Function(&'a mut Function),
+    Expr(&'a mut Expr),
+}
+
+impl Component<'_> {
+    pub(crate) fn visit_with<V: Visit>(&self, visitor: &mut V) {
+        match self {
+            Component::Function(function) => function.visit_with(visitor),
+            Component::Expr(expr) => expr.visit_with(visitor),
+        }
+    }
+
+    pub(crate) fn visit_mut_with<V: VisitMut>(&mut self, visitor: &mut V) {
+        match self {
+            Component::Function(function) => function.visit_mut_with(visitor),
+            Component::Expr(expr) => expr.visit_mut_with(visitor),
+        }
+    }
+}
+
+/// Returns the components a module item declares, along with their names.
+/// Default exports are named by [default_name].
+pub(crate) fn components<'a>(ctx: &Ctx, item: &'a mut ModuleItem) -> Vec<(String, Component<'a>)> {
+    match item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl)))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Fn(fn_decl),
+            ..
+        })) if is_component_name(ctx, &fn_decl.ident.sym) && is_component_function(ctx, &fn_decl.function) => {
+            vec![(fn_decl.ident.sym.to_string(), Component::Function(&mut fn_decl.function))]
+        }
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl)))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Var(var_decl),
+            ..
+        })) => var_decl
+            .decls
+            .iter_mut()
+            .filter_map(|decl| match (&decl.name, &mut decl.init) {
+                (Pat::Ident(binding), Some(init))
+                    if is_component_name(ctx, &binding.id.sym) && is_component_expr(ctx, init) =>
+                {
+                    Some((binding.id.sym.to_string(), Component::Expr(init)))
+                }
+                _ => None,
+            })
+            .collect(),
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+            decl: DefaultDecl::Fn(fn_expr),
+            ..
+        })) if is_default_component_function(ctx, fn_expr) => {
+            let name = default_name(fn_expr.ident.as_ref());
+            if names::is_skipped(ctx, &name) {
+                return Vec::new();
+            }
+            vec![(name, Component::Function(&mut fn_expr.function))]
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export))
+            if is_default_component_expr(ctx, &export.expr) =>
+        {
+            let name = default_export_name(&export.expr);
+            if names::is_skipped(ctx, &name) {
+                return Vec::new();
+            }
+            vec![(name, Component::Expr(&mut export.expr))]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Like [components], leaving out the components the observer doesn't wrap:
+/// private ones with `exported_only`, and static ones with `reactive_sources`
+pub(crate) fn wrapped_components<'a>(ctx: &Ctx, item: &'a mut ModuleItem) -> Vec<(String, Component<'a>)> {
+    let is_exported = matches!(item, ModuleItem::ModuleDecl(_));
+
+    components(ctx, item)
+        .into_iter()
+        .filter(|(name, component)| (is_exported || ctx.is_exported(name)) && ctx.is_reactive(component))
+        .collect()
+}
+
+/// The name of a default exported function, e.g. for `skip_components` and the
+/// `{componentName}` placeholder, `default` when it has none
+pub(crate) fn default_name(ident: Option<&Ident>) -> String {
+    ident.map_or("default".to_string(), |ident| ident.sym.to_string())
+}
+
+/// Like [default_name], for any default exported expression
+pub(crate) fn default_export_name(expr: &Expr) -> String {
+    match expr {
+        Expr::Fn(fn_expr) => default_name(fn_expr.ident.as_ref()),
+        _ => default_name(None),
+    }
+}
+
+/// Returns the local names of the top-level bindings a module exports, e.g.
+/// `A` for `export const A`, `export { A as B }` or `export default A`
+pub(crate) fn exported_names(module: &Module) -> HashSet<Atom> {
+    let mut names = HashSet::new();
+
+    for item in &module.body {
+        let ModuleItem::ModuleDecl(decl) = item else {
+            continue;
+        };
+
+        match decl {
+            ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => match decl {
+                Decl::Fn(fn_decl) => {
+                    names.insert(fn_decl.ident.sym.clone());
+                }
+                Decl::Class(class_decl) => {
+                    names.insert(class_decl.ident.sym.clone());
+                }
+                Decl::Var(var_decl) => {
+                    names.extend(var_decl.decls.iter().filter_map(|decl| match &decl.name {
+                        Pat::Ident(binding) => Some(binding.id.sym.clone()),
+                        _ => None,
+                    }));
+                }
+                _ => {}
+            },
+            // Re-exports of other modules don't export local bindings
+            ModuleDecl::ExportNamed(NamedExport { src: None, specifiers, .. }) => {
+                names.extend(specifiers.iter().filter_map(|specifier| match specifier {
+                    ExportSpecifier::Named(ExportNamedSpecifier {
+                        orig: ModuleExportName::Ident(orig),
+                        ..
+                    }) => Some(orig.sym.clone()),
+                    _ => None,
+                }));
+            }
+            ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { expr, .. }) => {
+                if let Expr::Ident(ident) = &**expr {
+                    names.insert(ident.sym.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
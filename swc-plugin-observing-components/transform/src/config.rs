@@ -0,0 +1,799 @@
+use serde::Deserialize;
+use std::fmt;
+use swc_cached::regex::CachedRegex;
+
+use crate::matcher;
+
+/// Options of the plugin. Keys can be spelled in snake_case or in camelCase,
+/// like the options of the Vite and Babel plugins.
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default, alias = "importName")]
+    pub import_name: Option<String>,
+    /// Module exporting the observer. A path starting with `/`, e.g.
+    /// `/src/lib/observer`, is relative to the cwd and imported relative to
+    /// each file.
+    #[serde(default, alias = "importPath")]
+    pub import_path: String,
+    /// Fills in the wrapper options for a known observer library. Options set
+    /// explicitly take precedence over the preset.
+    #[serde(default)]
+    pub preset: Option<Preset>,
+    /// How components are made observers, wrapping them in the observer by
+    /// default
+    #[serde(default)]
+    pub strategy: Option<Strategy>,
+    /// Method called on the value returned by the hook once the component has
+    /// rendered, `end` when not set. Only used by the `hook` strategy.
+    #[serde(default, alias = "hookCleanup")]
+    pub hook_cleanup: Option<String>,
+    /// Whether the observer is a named or the default export of `import_path`
+    #[serde(default, alias = "importStyle")]
+    pub import_style: Option<ImportStyle>,
+    /// Where the observer goes when a component is already wrapped in another
+    /// HOC, e.g. `observer(withRouter(fn))` or `withRouter(observer(fn))`
+    #[serde(default, alias = "hocOrder")]
+    pub hoc_order: Option<HocOrder>,
+    /// How components wrapped in `forwardRef` are handled. Follows `hoc_order`
+    /// when not set.
+    #[serde(default, alias = "forwardRef")]
+    pub forward_ref: Option<ForwardRef>,
+    /// Wrappers that make a component an observer just like `import_name`, so
+    /// components already wrapped in them are left alone
+    #[serde(default, alias = "equivalentWrappers")]
+    pub equivalent_wrappers: Option<Vec<String>>,
+    /// Options object passed to the observer after the component and any
+    /// comparator, see `observer_comparator`, e.g.
+    /// `{ "name": "{componentName}" }`. `{componentName}` and `{fileName}` in
+    /// its strings are replaced for every component. Not used when the
+    /// observer is listed in `wrappers`, which has its own `args`.
+    #[serde(default)]
+    pub options: Option<serde_json::Value>,
+    /// More HOCs to wrap components in, applied in order, so the first one is
+    /// the innermost. The observer is applied before them, unless it's listed
+    /// here too, e.g. to get `observer(withTracing(fn))`.
+    #[serde(default)]
+    pub wrappers: Vec<Wrapper>,
+    /// Render props to render through mobx's `<Observer>`, so what they render
+    /// is tracked even though other components call them. Off when not set.
+    #[serde(default, alias = "renderProps")]
+    pub render_props: Option<RenderProps>,
+    /// Lift inline `.map` callbacks returning JSX in components into observer
+    /// components of their own, so items re-render by themselves
+    #[serde(default, alias = "extractMapItems")]
+    pub extract_map_items: bool,
+    /// Which names are component names. By default any name starting with an
+    /// uppercase letter is.
+    #[serde(default, alias = "componentNames")]
+    pub component_names: ComponentNames,
+    /// Only make exported components observers, e.g. the public components of
+    /// a library, leaving the private ones of a module plain
+    #[serde(default, alias = "exportedOnly")]
+    pub exported_only: bool,
+    /// Modules exporting observables, e.g. `./stores` or `@app/state`. When
+    /// set, only components using their imports or receiving props are made
+    /// observers, leaving static components alone.
+    #[serde(default, alias = "reactiveSources")]
+    pub reactive_sources: Vec<String>,
+    /// Whether the observer takes a comparator like `memo`, e.g.
+    /// `observer(fn, areEqual)`, followed by any `options`. Components wrapped
+    /// in `memo` of `react` are wrapped in the observer instead, keeping their
+    /// comparator only then.
+    #[serde(default, alias = "observerComparator")]
+    pub observer_comparator: bool,
+    /// Names or globs of components that are never made observers, e.g. error
+    /// boundaries or `*Provider`. Skipped components are reported.
+    #[serde(default, alias = "skipComponents")]
+    pub skip_components: Vec<String>,
+    /// Functions creating lazily loaded components, e.g. `lazy(() => import("./Settings"))`,
+    /// which are never wrapped. `lazy` and `loadable` when not set.
+    #[serde(default, alias = "lazyFactories")]
+    pub lazy_factories: Option<Vec<String>>,
+    /// Also treat functions with component names that render no JSX as
+    /// components when they call hooks, e.g. `useUser()`, or read the imports
+    /// of `store_modules`
+    #[serde(default, alias = "detectHookComponents")]
+    pub detect_hook_components: bool,
+    /// Modules exporting stores, e.g. `./stores`, for `detect_hook_components`
+    #[serde(default, alias = "storeModules")]
+    pub store_modules: Vec<String>,
+    /// Wrap inline event handlers of components, e.g. `onClick={() => ...}`,
+    /// in mobx's `action`. Off when not set.
+    #[serde(default)]
+    pub actions: Option<Actions>,
+    /// Modules of React-compatible JSX runtimes. Files whose
+    /// `@jsxImportSource` pragma names another module, e.g. `solid-js`, are
+    /// left alone. `react`, `preact` and `@emotion/react` when not set.
+    #[serde(default, alias = "jsxImportSources")]
+    pub jsx_import_sources: Option<Vec<String>>,
+    /// Glob patterns a file must match to be transformed. All files are
+    /// included when empty.
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Names or globs of packages inside `node_modules` to transform, e.g.
+    /// linked workspace packages like `@app/*`. Other dependencies are skipped.
+    #[serde(default, alias = "includeNodeModules")]
+    pub include_node_modules: Vec<String>,
+    /// Match `include` and `exclude` patterns case-insensitively
+    #[serde(default, alias = "caseInsensitive")]
+    pub case_insensitive: bool,
+    /// Use the matching heuristics of earlier versions, which also match
+    /// patterns against the file name and any part of the path
+    #[serde(default, alias = "legacyExcludeMatching")]
+    pub legacy_exclude_matching: bool,
+    /// Wrapper options for specific files, e.g. `mobx-react` for legacy code.
+    /// The first rule matching a file is used, and files matching no rule use
+    /// the options above.
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+impl Config {
+    /// Parses and validates the JSON configuration passed to the plugin.
+    pub fn from_json(input: &str) -> Result<Self, ConfigError> {
+        parse(input).map(|(config, _)| config)
+    }
+
+    pub fn jsx_import_sources(&self) -> Vec<&str> {
+        match &self.jsx_import_sources {
+            Some(sources) => sources.iter().map(|source| source.as_str()).collect(),
+            None => vec!["react", "preact", "@emotion/react"],
+        }
+    }
+
+    pub fn lazy_factories(&self) -> Vec<&str> {
+        match &self.lazy_factories {
+            Some(factories) => factories.iter().map(|factory| factory.as_str()).collect(),
+            None => vec!["lazy", "loadable"],
+        }
+    }
+
+    // Fills in the options not set explicitly from the preset
+    fn apply_preset(&mut self) {
+        let Some(preset) = self.preset else {
+            return;
+        };
+        let defaults = preset.defaults();
+
+        if self.import_path.is_empty() {
+            self.import_path = defaults.import_path.into();
+        }
+        self.import_name.get_or_insert_with(|| defaults.import_name.into());
+        self.strategy.get_or_insert(defaults.strategy);
+        if self.hook_cleanup.is_none() {
+            self.hook_cleanup = defaults.hook_cleanup.map(|method| method.into());
+        }
+        self.import_style.get_or_insert(defaults.import_style);
+        self.hoc_order.get_or_insert(defaults.hoc_order);
+        if self.forward_ref.is_none() {
+            self.forward_ref = defaults.forward_ref;
+        }
+        self.equivalent_wrappers
+            .get_or_insert_with(|| defaults.equivalent_wrappers.iter().map(|name| name.to_string()).collect());
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), ConfigError> {
+        // Without `import_path` only the files matching a rule are transformed
+        if self.import_path.trim().is_empty() && self.rules.is_empty() {
+            return Err(ConfigError::Missing {
+                key: "import_path".into(),
+            });
+        }
+
+        if let Some(import_name) = &self.import_name {
+            if !is_identifier(import_name) {
+                return Err(ConfigError::InvalidValue {
+                    key: "import_name".into(),
+                    value: import_name.clone(),
+                    reason: "expected a JavaScript identifier".into(),
+                });
+            }
+        }
+
+        if let Some(hook_cleanup) = &self.hook_cleanup {
+            if !is_identifier(hook_cleanup) {
+                return Err(ConfigError::InvalidValue {
+                    key: "hook_cleanup".into(),
+                    value: hook_cleanup.clone(),
+                    reason: "expected a JavaScript identifier".into(),
+                });
+            }
+        }
+
+        if let Some(options) = &self.options {
+            if !options.is_object() {
+                return Err(ConfigError::InvalidValue {
+                    key: "options".into(),
+                    value: options.to_string(),
+                    reason: "expected an object".into(),
+                });
+            }
+        }
+
+        for (index, wrapper) in self.wrappers.iter().enumerate() {
+            wrapper.validate(&format!("wrappers[{}]", index))?;
+        }
+
+        // Components are left in place with the hook strategy, so wrappers
+        // would silently be ignored
+        if self.strategy == Some(Strategy::Hook) && !self.wrappers.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                key: "wrappers".into(),
+                value: self.wrappers.iter().map(|wrapper| wrapper.import_name.as_str()).collect::<Vec<_>>().join(", "),
+                reason: "can't be used with the `hook` strategy".into(),
+            });
+        }
+
+        if let Some(render_props) = &self.render_props {
+            render_props.validate()?;
+        }
+
+        if let Some(actions) = &self.actions {
+            actions.validate()?;
+        }
+
+        matcher::name_globs("skip_components", &self.skip_components)?;
+
+        if let Some(factory) = self.lazy_factories().into_iter().find(|factory| !is_identifier(factory)) {
+            return Err(ConfigError::InvalidValue {
+                key: "lazy_factories".into(),
+                value: factory.into(),
+                reason: "expected a JavaScript identifier".into(),
+            });
+        }
+
+        if let Some(source) = self.jsx_import_sources().into_iter().find(|source| source.trim().is_empty()) {
+            return Err(ConfigError::InvalidValue {
+                key: "jsx_import_sources".into(),
+                value: source.into(),
+                reason: "expected a module name".into(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+// Parses and validates the configuration, returning any warnings about it
+pub(crate) fn parse(input: &str) -> Result<(Config, Vec<String>), ConfigError> {
+    let value = if input.trim().is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_str::<serde_json::Value>(input)
+            .map_err(|err| ConfigError::Invalid(err.to_string()))?
+    };
+
+    if value.is_null() {
+        return Err(ConfigError::Invalid(
+            "expected an options object, e.g. { \"import_path\": \"mobx-react-lite\" }".into(),
+        ));
+    }
+
+    let warnings = mixed_key_styles(&value)
+        .map(|(snake_case, camel_case)| {
+            format!(
+                "observing-components config mixes snake_case (`{}`) and camelCase (`{}`) keys, \
+                 use one spelling consistently",
+                snake_case, camel_case
+            )
+        })
+        .into_iter()
+        .collect();
+
+    let mut config = serde_json::from_value::<Config>(value)
+        .map_err(|err| ConfigError::Invalid(err.to_string()))?;
+
+    config.apply_preset();
+
+    config.validate()?;
+
+    for (index, rule) in config.rules.iter().enumerate() {
+        rule.resolve(&config).validate().map_err(|err| err.in_rule(index))?;
+    }
+
+    Ok((config, warnings))
+}
+
+// Returns a snake_case and a camelCase key when both spellings are used
+fn mixed_key_styles(value: &serde_json::Value) -> Option<(String, String)> {
+    let mut snake_case = None;
+    let mut camel_case = None;
+    collect_key_styles(value, &mut snake_case, &mut camel_case);
+
+    snake_case.zip(camel_case)
+}
+
+// Keys of the configuration holding options of their own, spelled like the
+// rest. Other values, like `options` or the `args` of wrappers, are passed on
+// as is, so their keys aren't checked.
+const NESTED_KEYS: &[&str] = &[
+    "rules",
+    "wrappers",
+    "render_props",
+    "renderProps",
+    "actions",
+    "component_names",
+    "componentNames",
+];
+
+fn collect_key_styles(value: &serde_json::Value, snake_case: &mut Option<String>, camel_case: &mut Option<String>) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object {
+                if key.contains('_') {
+                    snake_case.get_or_insert_with(|| key.clone());
+                } else if key.chars().any(|c| c.is_ascii_uppercase()) {
+                    camel_case.get_or_insert_with(|| key.clone());
+                }
+
+                if NESTED_KEYS.contains(&key.as_str()) {
+                    collect_key_styles(value, snake_case, camel_case);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                collect_key_styles(value, snake_case, camel_case);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Wrapper options for the files matching `include` and `exclude`. Options not
+/// set fall back to the preset of the rule, then to the top-level options.
+///
+/// Rules only select files by pattern, so files inside `node_modules` always
+/// use the top-level options.
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    /// Glob patterns a file must match for the rule to apply. Matches all
+    /// files when empty.
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default, alias = "importName")]
+    pub import_name: Option<String>,
+    #[serde(default, alias = "importPath")]
+    pub import_path: Option<String>,
+    #[serde(default)]
+    pub preset: Option<Preset>,
+    #[serde(default)]
+    pub strategy: Option<Strategy>,
+    #[serde(default, alias = "hookCleanup")]
+    pub hook_cleanup: Option<String>,
+    #[serde(default, alias = "importStyle")]
+    pub import_style: Option<ImportStyle>,
+    #[serde(default, alias = "hocOrder")]
+    pub hoc_order: Option<HocOrder>,
+    #[serde(default, alias = "forwardRef")]
+    pub forward_ref: Option<ForwardRef>,
+    #[serde(default, alias = "equivalentWrappers")]
+    pub equivalent_wrappers: Option<Vec<String>>,
+    #[serde(default)]
+    pub options: Option<serde_json::Value>,
+    #[serde(default)]
+    pub wrappers: Option<Vec<Wrapper>>,
+}
+
+impl Rule {
+    /// Returns the options for the files matching the rule. Its `include` and
+    /// `exclude` are the patterns of the rule.
+    pub fn resolve(&self, config: &Config) -> Config {
+        let mut resolved = Config {
+            import_name: self.import_name.clone(),
+            import_path: self.import_path.clone().unwrap_or_default(),
+            preset: self.preset,
+            strategy: self.strategy,
+            hook_cleanup: self.hook_cleanup.clone(),
+            import_style: self.import_style,
+            hoc_order: self.hoc_order,
+            forward_ref: self.forward_ref,
+            equivalent_wrappers: self.equivalent_wrappers.clone(),
+            options: self.options.clone(),
+            wrappers: self.wrappers.clone().unwrap_or_else(|| config.wrappers.clone()),
+            render_props: config.render_props.clone(),
+            extract_map_items: config.extract_map_items,
+            component_names: config.component_names.clone(),
+            exported_only: config.exported_only,
+            reactive_sources: config.reactive_sources.clone(),
+            observer_comparator: config.observer_comparator,
+            skip_components: config.skip_components.clone(),
+            lazy_factories: config.lazy_factories.clone(),
+            detect_hook_components: config.detect_hook_components,
+            store_modules: config.store_modules.clone(),
+            actions: config.actions.clone(),
+            jsx_import_sources: config.jsx_import_sources.clone(),
+            include: self.include.clone(),
+            exclude: self.exclude.clone(),
+            include_node_modules: Vec::new(),
+            case_insensitive: config.case_insensitive,
+            legacy_exclude_matching: config.legacy_exclude_matching,
+            rules: Vec::new(),
+        };
+
+        resolved.apply_preset();
+
+        if resolved.import_path.is_empty() {
+            resolved.import_path = config.import_path.clone();
+        }
+        if resolved.import_name.is_none() {
+            resolved.import_name = config.import_name.clone();
+        }
+        resolved.strategy = resolved.strategy.or(config.strategy);
+        if resolved.hook_cleanup.is_none() {
+            resolved.hook_cleanup = config.hook_cleanup.clone();
+        }
+        resolved.import_style = resolved.import_style.or(config.import_style);
+        resolved.hoc_order = resolved.hoc_order.or(config.hoc_order);
+        resolved.forward_ref = resolved.forward_ref.or(config.forward_ref);
+        if resolved.equivalent_wrappers.is_none() {
+            resolved.equivalent_wrappers = config.equivalent_wrappers.clone();
+        }
+        if resolved.options.is_none() {
+            resolved.options = config.options.clone();
+        }
+
+        resolved
+    }
+}
+
+/// A HOC components are wrapped in, e.g. `withErrorBoundary` from
+/// `react-error-boundary`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Wrapper {
+    #[serde(alias = "importPath")]
+    pub import_path: String,
+    #[serde(alias = "importName")]
+    pub import_name: String,
+    #[serde(default, alias = "importStyle")]
+    pub import_style: ImportStyle,
+    /// Arguments passed after the component, e.g. `[{ "fallback": null }]`.
+    /// Placeholders are replaced like in `options`.
+    #[serde(default)]
+    pub args: Vec<serde_json::Value>,
+}
+
+impl Wrapper {
+    fn validate(&self, key: &str) -> Result<(), ConfigError> {
+        if self.import_path.trim().is_empty() {
+            return Err(ConfigError::Missing {
+                key: format!("{}.import_path", key),
+            });
+        }
+
+        if !is_identifier(&self.import_name) {
+            return Err(ConfigError::InvalidValue {
+                key: format!("{}.import_name", key),
+                value: self.import_name.clone(),
+                reason: "expected a JavaScript identifier".into(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Render props rewritten to render through `<Observer>`, e.g.
+/// `renderItem={item => <Observer>{() => <Row item={item} />}</Observer>}`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RenderProps {
+    /// Names of the attributes taking a render function, e.g. `renderItem`
+    #[serde(default)]
+    pub attributes: Vec<String>,
+    /// Also rewrite functions passed as children, e.g. `<Query>{data => ...}</Query>`
+    #[serde(default)]
+    pub children: bool,
+    /// Module exporting `<Observer>`, the `import_path` of the plugin when not
+    /// set
+    #[serde(default, alias = "importPath")]
+    pub import_path: Option<String>,
+    /// `Observer` when not set
+    #[serde(default, alias = "importName")]
+    pub import_name: Option<String>,
+}
+
+impl RenderProps {
+    pub fn observer_name(&self) -> &str {
+        self.import_name.as_deref().unwrap_or("Observer")
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !is_identifier(self.observer_name()) {
+            return Err(ConfigError::InvalidValue {
+                key: "render_props.import_name".into(),
+                value: self.observer_name().into(),
+                reason: "expected a JavaScript identifier".into(),
+            });
+        }
+
+        if self.import_path.as_deref().is_some_and(|path| path.trim().is_empty()) {
+            return Err(ConfigError::Missing {
+                key: "render_props.import_path".into(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// The `action` inline event handlers are wrapped in, e.g.
+/// `onClick={action(() => store.count++)}`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Actions {
+    /// `mobx` when not set
+    #[serde(default, alias = "importPath")]
+    pub import_path: Option<String>,
+    /// `action` when not set
+    #[serde(default, alias = "importName")]
+    pub import_name: Option<String>,
+}
+
+impl Actions {
+    pub fn action_path(&self) -> &str {
+        self.import_path.as_deref().unwrap_or("mobx")
+    }
+
+    pub fn action_name(&self) -> &str {
+        self.import_name.as_deref().unwrap_or("action")
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !is_identifier(self.action_name()) {
+            return Err(ConfigError::InvalidValue {
+                key: "actions.import_name".into(),
+                value: self.action_name().into(),
+                reason: "expected a JavaScript identifier".into(),
+            });
+        }
+
+        if self.action_path().trim().is_empty() {
+            return Err(ConfigError::Missing {
+                key: "actions.import_path".into(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Decides which functions and variables are named like components. Explicit
+/// `allow` and `deny` lists win over the patterns.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ComponentNames {
+    /// Only accept PascalCase names, e.g. `TodoList` but not `API_VIEW`. A
+    /// leading `$` is ignored.
+    #[serde(default, alias = "pascalCase")]
+    pub pascal_case: bool,
+    /// Regular expressions a name must match one of, e.g. `^\$[A-Z]` for
+    /// `$Component`. Names starting with an uppercase letter when empty.
+    #[serde(default)]
+    pub include: Vec<CachedRegex>,
+    /// Regular expressions of names that aren't components, e.g. `^[A-Z_]+$`
+    #[serde(default)]
+    pub exclude: Vec<CachedRegex>,
+    /// Names always treated as components
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Names never treated as components
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl ComponentNames {
+    pub fn is_match(&self, name: &str) -> bool {
+        if self.deny.iter().any(|denied| denied == name) {
+            return false;
+        }
+        if self.allow.iter().any(|allowed| allowed == name) {
+            return true;
+        }
+
+        let is_included = if self.include.is_empty() {
+            name.chars().next().is_some_and(|first| first.is_uppercase())
+        } else {
+            self.include.iter().any(|include| include.is_match(name))
+        };
+
+        is_included
+            && !self.exclude.iter().any(|exclude| exclude.is_match(name))
+            && (!self.pascal_case || is_pascal_case(name))
+    }
+}
+
+// Checks for e.g. `TodoList` or `APIView`, but not `API_VIEW` or `TODO`
+fn is_pascal_case(name: &str) -> bool {
+    let name = name.strip_prefix('$').unwrap_or(name);
+    name.chars().next().is_some_and(|first| first.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+        && name.chars().any(|c| c.is_ascii_lowercase())
+}
+
+/// Observer libraries with known defaults for the wrapper options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    MobxReact,
+    MobxReactLite,
+    LegendState,
+    PreactSignals,
+}
+
+struct PresetDefaults {
+    import_path: &'static str,
+    import_name: &'static str,
+    strategy: Strategy,
+    hook_cleanup: Option<&'static str>,
+    import_style: ImportStyle,
+    hoc_order: HocOrder,
+    forward_ref: Option<ForwardRef>,
+    equivalent_wrappers: &'static [&'static str],
+}
+
+impl Preset {
+    fn defaults(self) -> PresetDefaults {
+        match self {
+            // MobX wants observer applied first, and supports observer(forwardRef(fn))
+            Preset::MobxReact => PresetDefaults {
+                import_path: "mobx-react",
+                import_name: "observer",
+                strategy: Strategy::Hoc,
+                hook_cleanup: None,
+                import_style: ImportStyle::Named,
+                hoc_order: HocOrder::Inner,
+                forward_ref: Some(ForwardRef::Outer),
+                equivalent_wrappers: &["observer"],
+            },
+            Preset::MobxReactLite => PresetDefaults {
+                import_path: "mobx-react-lite",
+                import_name: "observer",
+                strategy: Strategy::Hoc,
+                hook_cleanup: None,
+                import_style: ImportStyle::Named,
+                hoc_order: HocOrder::Inner,
+                forward_ref: Some(ForwardRef::Outer),
+                equivalent_wrappers: &["observer"],
+            },
+            Preset::LegendState => PresetDefaults {
+                import_path: "@legendapp/state/react",
+                import_name: "observer",
+                strategy: Strategy::Hoc,
+                hook_cleanup: None,
+                import_style: ImportStyle::Named,
+                hoc_order: HocOrder::Outer,
+                forward_ref: Some(ForwardRef::Outer),
+                equivalent_wrappers: &["observer", "reactive", "reactiveObserver"],
+            },
+            Preset::PreactSignals => PresetDefaults {
+                import_path: "@preact/signals-react/runtime",
+                import_name: "useSignals",
+                strategy: Strategy::Hook,
+                hook_cleanup: Some("f"),
+                import_style: ImportStyle::Named,
+                hoc_order: HocOrder::Outer,
+                forward_ref: None,
+                equivalent_wrappers: &[],
+            },
+        }
+    }
+}
+
+/// How components are made observers
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strategy {
+    /// `const Home = observer(() => <div />)`
+    #[default]
+    Hoc,
+    /// Calls `import_name` as a hook at the top of every component, and the
+    /// `hook_cleanup` method on its result once it has rendered:
+    /// `const _t = useTracking(); try { ... } finally { _t.end() }`.
+    /// Components are left in place, so `wrappers` can't be set.
+    Hook,
+}
+
+/// How the observer is imported from `import_path`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStyle {
+    /// `import { observer } from "..."`
+    #[default]
+    Named,
+    /// `import observer from "..."`
+    Default,
+}
+
+/// Where the observer goes relative to other HOCs wrapping a component
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HocOrder {
+    /// `observer(withRouter(fn))`
+    #[default]
+    Outer,
+    /// `withRouter(observer(fn))`
+    Inner,
+}
+
+/// How components wrapped in `forwardRef` are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForwardRef {
+    /// `observer(forwardRef(fn))`
+    Outer,
+    /// `forwardRef(observer(fn))`
+    Inner,
+    /// Leave them unwrapped
+    Skip,
+}
+
+pub(crate) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_alphabetic() || first == '_' || first == '$' => {
+            chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        }
+        _ => false,
+    }
+}
+
+/// An invalid plugin configuration, reported through SWC's diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The configuration isn't valid JSON, has an unknown key or a value of
+    /// the wrong type
+    Invalid(String),
+    /// A required option is missing
+    Missing { key: String },
+    /// An option has a value that can't be used
+    InvalidValue {
+        key: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Invalid(message) => {
+                write!(f, "invalid observing-components config: {}", message)
+            }
+            ConfigError::Missing { key } => {
+                write!(f, "missing `{}` in observing-components config", key)
+            }
+            ConfigError::InvalidValue { key, value, reason } => write!(
+                f,
+                "invalid value {:?} for `{}` in observing-components config: {}",
+                value, key, reason
+            ),
+        }
+    }
+}
+
+impl ConfigError {
+    // Qualifies the key of the error with the rule it was found in
+    pub(crate) fn in_rule(self, index: usize) -> Self {
+        match self {
+            ConfigError::Invalid(message) => ConfigError::Invalid(format!("`rules[{}]`: {}", index, message)),
+            ConfigError::Missing { key } => ConfigError::Missing {
+                key: format!("rules[{}].{}", index, key),
+            },
+            ConfigError::InvalidValue { key, value, reason } => ConfigError::InvalidValue {
+                key: format!("rules[{}].{}", index, key),
+                value,
+                reason,
+            },
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
@@ -0,0 +1,54 @@
+//! What the checks for components know about the module being transformed
+
+use std::collections::HashSet;
+
+use swc_atoms::Atom;
+use swc_ecma_ast::*;
+
+use crate::{
+    components::{self, Component},
+    hooks::HookDetection,
+    jsx::{JsxFactories, JsxPragmas},
+    names::Names,
+    reactive::ReactiveSources,
+    Config,
+};
+
+/// The options and imports of a module the checks for components depend on,
+/// built once per module and passed to them
+#[derive(Debug, Default)]
+pub(crate) struct Ctx {
+    pub(crate) names: Names,
+    pub(crate) jsx: JsxFactories,
+    /// Set with `detect_hook_components`
+    pub(crate) hooks: Option<HookDetection>,
+    /// The exported top-level bindings, set with `exported_only`
+    pub(crate) exported: Option<HashSet<Atom>>,
+    /// The imports of the `reactive_sources`, when set
+    pub(crate) reactive: Option<ReactiveSources>,
+}
+
+impl Ctx {
+    pub(crate) fn new(module: &Module, config: &Config, pragmas: &JsxPragmas) -> Self {
+        Ctx {
+            names: Names::new(config),
+            jsx: JsxFactories::new(module, pragmas, &config.jsx_import_sources()),
+            hooks: config.detect_hook_components.then(|| HookDetection::new(module, config)),
+            exported: config.exported_only.then(|| components::exported_names(module)),
+            reactive: (!config.reactive_sources.is_empty())
+                .then(|| ReactiveSources::new(module, &config.reactive_sources)),
+        }
+    }
+
+    /// Whether a top-level binding can be made an observer, which with
+    /// `exported_only` requires it to be exported
+    pub(crate) fn is_exported(&self, name: &str) -> bool {
+        self.exported.as_ref().map_or(true, |exported| exported.contains(&Atom::from(name)))
+    }
+
+    /// Whether a component can read observables, judging by the
+    /// `reactive_sources` when set
+    pub(crate) fn is_reactive(&self, component: &Component) -> bool {
+        self.reactive.as_ref().map_or(true, |reactive| reactive.reason(self, component).is_some())
+    }
+}
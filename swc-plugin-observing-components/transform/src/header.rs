@@ -0,0 +1,85 @@
+//! Per-module options set in a header comment
+
+use serde::de::DeserializeOwned;
+
+use crate::{Config, ConfigError, Rule};
+
+const TAG: &str = "@observing-components";
+
+/// Applies the options of an `@observing-components` comment on top of the
+/// configuration, e.g. `/** @observing-components importPath=./observer */`.
+/// Returns `None` when the comment has no such tag.
+pub(crate) fn header_config(comment: &str, config: &Config) -> Option<Result<Config, ConfigError>> {
+    let options = tag_options(comment)?;
+
+    let result = parse_options(options).and_then(|rule| {
+        let config = rule.resolve(config);
+        config.validate()?;
+        Ok(config)
+    });
+
+    Some(result)
+}
+
+// Returns what follows the tag, skipping e.g. `@observing-components-ignore`
+fn tag_options(comment: &str) -> Option<&str> {
+    let (_, options) = comment.split_once(TAG)?;
+
+    match options.chars().next() {
+        None => Some(options),
+        Some(c) if c.is_whitespace() => Some(options),
+        _ => None,
+    }
+}
+
+// Parses the `key=value` pairs of the comment. Keys can be spelled in
+// snake_case or in camelCase, like in the plugin options.
+fn parse_options(options: &str) -> Result<Rule, ConfigError> {
+    let mut rule = Rule::default();
+
+    // Leading `*` of JSDoc style comments spanning multiple lines are skipped
+    for option in options.split_whitespace().filter(|word| *word != "*") {
+        let Some((key, value)) = option.split_once('=') else {
+            return Err(ConfigError::Invalid(format!(
+                "expected `key=value` in `{}` comment, found `{}`",
+                TAG, option
+            )));
+        };
+
+        match key {
+            "import_path" | "importPath" => rule.import_path = Some(value.into()),
+            "import_name" | "importName" => rule.import_name = Some(value.into()),
+            "preset" => rule.preset = Some(parse_value(key, value)?),
+            "strategy" => rule.strategy = Some(parse_value(key, value)?),
+            "hook_cleanup" | "hookCleanup" => rule.hook_cleanup = Some(value.into()),
+            "import_style" | "importStyle" => rule.import_style = Some(parse_value(key, value)?),
+            "hoc_order" | "hocOrder" => rule.hoc_order = Some(parse_value(key, value)?),
+            "forward_ref" | "forwardRef" => rule.forward_ref = Some(parse_value(key, value)?),
+            "equivalent_wrappers" | "equivalentWrappers" => {
+                rule.equivalent_wrappers = Some(
+                    value
+                        .split(',')
+                        .filter(|name| !name.is_empty())
+                        .map(|name| name.to_string())
+                        .collect(),
+                );
+            }
+            _ => {
+                return Err(ConfigError::Invalid(format!(
+                    "unknown option `{}` in `{}` comment",
+                    key, TAG
+                )));
+            }
+        }
+    }
+
+    Ok(rule)
+}
+
+fn parse_value<T: DeserializeOwned>(key: &str, value: &str) -> Result<T, ConfigError> {
+    serde_json::from_value(serde_json::Value::String(value.into())).map_err(|err| ConfigError::InvalidValue {
+        key: key.into(),
+        value: value.into(),
+        reason: err.to_string(),
+    })
+}
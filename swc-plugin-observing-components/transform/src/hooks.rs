@@ -0,0 +1,115 @@
+//! Components rendering no JSX, detected by the hooks they call
+
+use swc_atoms::Atom;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{context::Ctx, Config};
+
+/// How the module being transformed is searched for components without JSX,
+/// e.g. `function Layout({ children }) { useUser(); return children; }`. Only
+/// set with `detect_hook_components`.
+#[derive(Debug)]
+pub(crate) struct HookDetection {
+    /// Local names of the imports of `store_modules`
+    stores: Vec<Atom>,
+}
+
+impl HookDetection {
+    pub(crate) fn new(module: &Module, config: &Config) -> Self {
+        let stores = module
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+                    if config.store_modules.iter().any(|store| *store == *import.src.value) =>
+                {
+                    Some(import)
+                }
+                _ => None,
+            })
+            .flat_map(|import| import.specifiers.iter())
+            .map(|specifier| match specifier {
+                ImportSpecifier::Named(named) => named.local.sym.clone(),
+                ImportSpecifier::Default(default) => default.local.sym.clone(),
+                ImportSpecifier::Namespace(namespace) => namespace.local.sym.clone(),
+            })
+            .collect();
+
+        HookDetection { stores }
+    }
+}
+
+/// Checks whether a function calls hooks or reads stores, when
+/// `detect_hook_components` is set
+pub(crate) fn uses_hooks_in_function(ctx: &Ctx, function: &Function) -> bool {
+    find_hooks(ctx, |finder| function.body.visit_with(finder))
+}
+
+/// Like [uses_hooks_in_function], for function expressions and functions
+/// passed to HOCs, e.g. `memo(() => ...)`
+pub(crate) fn uses_hooks_in_expr(ctx: &Ctx, expr: &Expr) -> bool {
+    match expr {
+        Expr::Paren(paren) => uses_hooks_in_expr(ctx, &paren.expr),
+        Expr::Fn(fn_expr) => uses_hooks_in_function(ctx, &fn_expr.function),
+        Expr::Arrow(arrow) => find_hooks(ctx, |finder| arrow.body.visit_with(finder)),
+        Expr::Call(call_expr) => call_expr.args.iter().any(|arg| uses_hooks_in_expr(ctx, &arg.expr)),
+        _ => false,
+    }
+}
+
+fn find_hooks(ctx: &Ctx, visit: impl FnOnce(&mut HookFinder)) -> bool {
+    let Some(detection) = &ctx.hooks else {
+        return false;
+    };
+
+    let mut finder = HookFinder {
+        stores: &detection.stores,
+        found: false,
+    };
+    visit(&mut finder);
+    finder.found
+}
+
+struct HookFinder<'a> {
+    stores: &'a [Atom],
+    found: bool,
+}
+
+impl Visit for HookFinder<'_> {
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        if let Callee::Expr(callee) = &call_expr.callee {
+            let name = match &**callee {
+                Expr::Ident(ident) => Some(&ident.sym),
+                Expr::Member(member) => member.prop.as_ident().map(|prop| &prop.sym),
+                _ => None,
+            };
+            if name.is_some_and(|name| is_hook_name(name)) {
+                self.found = true;
+                return;
+            }
+        }
+
+        call_expr.visit_children_with(self);
+    }
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        if self.stores.contains(&ident.sym) {
+            self.found = true;
+        }
+    }
+
+    // Hooks can't be called in nested functions, and what those read isn't
+    // read while rendering
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+
+    fn visit_class(&mut self, _: &Class) {}
+}
+
+// Checks for `use`, `useState`, `useUser` etc.
+pub(crate) fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use")
+        .is_some_and(|rest| rest.chars().next().map_or(true, |first| first.is_ascii_uppercase()))
+}
@@ -0,0 +1,55 @@
+//! Project-root relative import paths
+
+use std::borrow::Cow;
+
+/// Rewrites an `import_path` starting with `/`, e.g. `/src/lib/observer`, into
+/// a specifier relative to the file, e.g. `../../lib/observer`. Such paths are
+/// resolved against the cwd, so apps can import their own observer without a
+/// path alias. Other paths, and any path when the cwd or the file name is
+/// unknown, are returned as is.
+pub fn resolve_import_path<'a>(import_path: &'a str, file_path: &str, cwd: Option<&str>) -> Cow<'a, str> {
+    let Some(root_path) = import_path.strip_prefix('/') else {
+        return Cow::Borrowed(import_path);
+    };
+
+    let cwd = match cwd {
+        Some(cwd) if !cwd.is_empty() && !file_path.is_empty() => cwd,
+        _ => return Cow::Borrowed(import_path),
+    };
+
+    let cwd = segments(cwd);
+    let target: Vec<&str> = cwd.iter().copied().chain(segments(root_path)).collect();
+
+    // File names are usually absolute, but relative ones are relative to the cwd
+    let mut file: Vec<&str> = if is_absolute(file_path) {
+        segments(file_path)
+    } else {
+        cwd.iter().copied().chain(segments(file_path)).collect()
+    };
+    file.pop();
+
+    let common = file
+        .iter()
+        .zip(&target)
+        .take_while(|(file, target)| file == target)
+        .count();
+
+    let mut specifier = match file.len() - common {
+        0 => String::from("./"),
+        ups => "../".repeat(ups),
+    };
+    specifier.push_str(&target[common..].join("/"));
+
+    Cow::Owned(specifier)
+}
+
+// Splits a path on both separators, so Windows paths are handled as well
+fn segments(path: &str) -> Vec<&str> {
+    path.split(['/', '\\'])
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect()
+}
+
+fn is_absolute(path: &str) -> bool {
+    path.starts_with(['/', '\\']) || path.get(1..3).is_some_and(|drive| drive == ":\\" || drive == ":/")
+}
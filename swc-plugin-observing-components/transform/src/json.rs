@@ -0,0 +1,75 @@
+//! JSON values from the configuration as JavaScript expressions
+
+use swc_ecma_ast::*;
+
+use crate::config::is_identifier;
+
+/// Creates the literal for a JSON value, e.g. `{ fallback: null }`. The
+/// placeholders are replaced in its strings, e.g. `{componentName}`, and the
+/// keys of objects keep the order of the configuration.
+pub(crate) fn json_expr(value: &serde_json::Value, placeholders: &[(&str, &str)]) -> Expr {
+    match value {
+        serde_json::Value::Null => Expr::Lit(Lit::Null(Null {
+            span: Default::default(),
+        })),
+        serde_json::Value::Bool(value) => Expr::Lit(Lit::Bool(Bool {
+            span: Default::default(),
+            value: *value,
+        })),
+        serde_json::Value::Number(number) => Expr::Lit(Lit::Num(Number {
+            span: Default::default(),
+            value: number.as_f64().unwrap_or_default(),
+            raw: None,
+        })),
+        serde_json::Value::String(value) => {
+            let value = placeholders
+                .iter()
+                .fold(value.clone(), |value, (placeholder, replacement)| {
+                    value.replace(placeholder, replacement)
+                });
+
+            Expr::Lit(Lit::Str(Str {
+                span: Default::default(),
+                value: value.into(),
+                raw: None,
+            }))
+        }
+        serde_json::Value::Array(values) => Expr::Array(ArrayLit {
+            span: Default::default(),
+            elems: values
+                .iter()
+                .map(|value| {
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(json_expr(value, placeholders)),
+                    })
+                })
+                .collect(),
+        }),
+        serde_json::Value::Object(object) => Expr::Object(ObjectLit {
+            span: Default::default(),
+            props: object
+                .iter()
+                .map(|(key, value)| {
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: prop_name(key),
+                        value: Box::new(json_expr(value, placeholders)),
+                    })))
+                })
+                .collect(),
+        }),
+    }
+}
+
+// Keys that aren't identifiers are quoted
+fn prop_name(key: &str) -> PropName {
+    if is_identifier(key) {
+        PropName::Ident(IdentName::new(key.into(), Default::default()))
+    } else {
+        PropName::Str(Str {
+            span: Default::default(),
+            value: key.into(),
+            raw: None,
+        })
+    }
+}
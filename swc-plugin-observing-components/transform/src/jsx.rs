@@ -0,0 +1,173 @@
+//! JSX compiled into function calls, and the runtime JSX targets
+
+use swc_atoms::Atom;
+use swc_common::comments::Comments;
+use swc_ecma_ast::*;
+
+use crate::context::Ctx;
+
+// Functions the automatic runtime compiles JSX into, exported by e.g.
+// `react/jsx-runtime`
+const RUNTIME_FUNCTIONS: &[&str] = &["jsx", "jsxs", "jsxDEV"];
+const RUNTIME_MODULES: &[&str] = &["jsx-runtime", "jsx-dev-runtime"];
+
+/// The JSX pragmas of a module, e.g. `/** @jsxImportSource solid-js */` or
+/// `/** @jsx h */`
+#[derive(Debug, Default)]
+pub(crate) struct JsxPragmas {
+    import_source: Option<String>,
+    factory: Option<String>,
+}
+
+impl JsxPragmas {
+    pub(crate) fn parse<'a>(comments: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut pragmas = JsxPragmas::default();
+
+        for comment in comments {
+            let mut words = comment.split_whitespace();
+            while let Some(word) = words.next() {
+                let value = match word {
+                    "@jsxImportSource" => &mut pragmas.import_source,
+                    "@jsx" => &mut pragmas.factory,
+                    _ => continue,
+                };
+                *value = words.next().map(|value| value.to_string());
+            }
+        }
+
+        pragmas
+    }
+
+    /// Reads the pragmas from the leading comments of a module
+    pub(crate) fn of_module<C: Comments>(module: &Module, comments: &C) -> Self {
+        let leading = comments.get_leading(module.span.lo).unwrap_or_default();
+        Self::parse(leading.iter().map(|comment| &*comment.text))
+    }
+}
+
+/// Checks whether the JSX of a module targets one of the React-compatible
+/// runtimes in `sources`, judging by its pragmas. Modules without pragmas are
+/// assumed to.
+pub(crate) fn targets_react(module: &Module, pragmas: &JsxPragmas, sources: &[&str]) -> bool {
+    if let Some(import_source) = &pragmas.import_source {
+        return sources.contains(&import_source.as_str());
+    }
+
+    // A factory imported from another library, e.g. `@jsx h` with
+    // `import { h } from "vue"`
+    let factory = pragmas.factory.as_deref().and_then(|factory| factory.split('.').next());
+    let Some(factory) = factory else {
+        return true;
+    };
+
+    imports(module)
+        .filter(|(_, _, local)| local.sym == *factory)
+        .all(|(source, _, _)| is_from(source, sources))
+}
+
+// Checks for a module of one of the sources, e.g. `preact/compat` for `preact`
+fn is_from(module: &str, sources: &[&str]) -> bool {
+    sources.iter().any(|source| {
+        module
+            .strip_prefix(source)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+// The imports of a module as `(source, imported name, local)`, with `default`
+// and `*` as the imported name of default and namespace imports
+pub(crate) fn imports(module: &Module) -> impl Iterator<Item = (&str, &str, &Ident)> {
+    module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => Some(import),
+            _ => None,
+        })
+        .flat_map(|import| {
+            import.specifiers.iter().map(move |specifier| {
+                let (imported, local) = match specifier {
+                    ImportSpecifier::Named(named) => {
+                        let imported = match &named.imported {
+                            Some(ModuleExportName::Ident(ident)) => &*ident.sym,
+                            Some(ModuleExportName::Str(name)) => &*name.value,
+                            None => &*named.local.sym,
+                        };
+                        (imported, &named.local)
+                    }
+                    ImportSpecifier::Default(default) => ("default", &default.local),
+                    ImportSpecifier::Namespace(namespace) => ("*", &namespace.local),
+                };
+                (&*import.src.value, imported, local)
+            })
+        })
+}
+
+/// The functions JSX of the module being transformed was compiled into, so
+/// components are found even when the plugin runs after the JSX transform.
+#[derive(Debug, Default)]
+pub(crate) struct JsxFactories {
+    /// Local names of `createElement`, of the runtime functions, e.g. `_jsx`
+    /// for `import { jsx as _jsx } from "react/jsx-runtime"`, and of the
+    /// `@jsx` pragma
+    functions: Vec<Atom>,
+    /// Factories called on an object, e.g. `React.createElement`
+    methods: Vec<(Atom, Atom)>,
+}
+
+impl JsxFactories {
+    pub(crate) fn new(module: &Module, pragmas: &JsxPragmas, sources: &[&str]) -> Self {
+        // The classic runtime expects `React` in scope, imported or not
+        let mut factories = JsxFactories {
+            functions: Vec::new(),
+            methods: vec![("React".into(), "createElement".into())],
+        };
+
+        for (source, imported, local) in imports(module) {
+            let is_runtime = sources.iter().any(|jsx_source| {
+                RUNTIME_MODULES
+                    .iter()
+                    .any(|runtime| source.strip_prefix(jsx_source) == Some(&format!("/{}", runtime)))
+            });
+
+            if (is_runtime && RUNTIME_FUNCTIONS.contains(&imported)) || (source == "react" && imported == "createElement") {
+                factories.functions.push(local.sym.clone());
+            } else if source == "react" && (imported == "default" || imported == "*") {
+                factories.methods.push((local.sym.clone(), "createElement".into()));
+            }
+        }
+
+        match pragmas.factory.as_deref().map(|factory| factory.split_once('.')) {
+            Some(Some((object, method))) => factories.methods.push((object.into(), method.into())),
+            Some(None) => factories.functions.extend(pragmas.factory.as_deref().map(Atom::from)),
+            None => {}
+        }
+
+        factories
+    }
+
+    fn is_factory(&self, callee: &Expr) -> bool {
+        match callee {
+            Expr::Ident(ident) => self.functions.contains(&ident.sym),
+            Expr::Member(member) => match (&*member.obj, member.prop.as_ident()) {
+                (Expr::Ident(object), Some(method)) => self
+                    .methods
+                    .iter()
+                    .any(|(factory_object, factory_method)| object.sym == *factory_object && method.sym == *factory_method),
+                _ => false,
+            },
+            Expr::Paren(paren) => self.is_factory(&paren.expr),
+            _ => false,
+        }
+    }
+}
+
+/// Checks for compiled JSX, e.g. `React.createElement("div")`, `_jsx("div", {})`
+/// or `h("div")` with `/** @jsx h */`
+pub(crate) fn is_jsx_call(ctx: &Ctx, call_expr: &CallExpr) -> bool {
+    let Callee::Expr(callee) = &call_expr.callee else {
+        return false;
+    };
+
+    ctx.jsx.is_factory(callee)
+}
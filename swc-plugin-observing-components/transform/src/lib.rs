@@ -1,197 +1,836 @@
+use std::cell::Cell;
+
+use swc_common::{comments::Comments, errors::HANDLER, Mark, Span, Spanned, SyntaxContext};
 use swc_ecma_ast::*;
-use swc_ecma_visit::{fold_pass, noop_fold_type, Fold};
-use serde::Deserialize;
-use globset::{Glob, GlobSet, GlobSetBuilder};
-use std::path::{Path, PathBuf};
-
-#[derive(Debug, Deserialize, Clone)]
-pub struct Config {
-    #[serde(default)]
-    pub import_name: Option<String>,
-    pub import_path: String,
-    #[serde(default)]
-    pub exclude: Vec<String>,
+use swc_ecma_visit::{fold_pass, noop_fold_type, Fold, VisitMutWith};
+
+use crate::{
+    components::{default_export_name, default_name},
+    context::Ctx,
+    jsx::JsxPragmas,
+    memo::ReactMemo,
+    reactive::{Reason, ReactiveSources},
+};
+pub(crate) use crate::names::is_component_name;
+
+mod actions;
+mod cached;
+mod components;
+mod config;
+mod context;
+mod header;
+mod hooks;
+mod import_path;
+mod json;
+mod jsx;
+mod map_items;
+mod memo;
+mod matcher;
+mod names;
+mod reactive;
+mod render_props;
+#[cfg(test)]
+mod test;
+
+pub use actions::action_transform;
+pub use cached::{CachedConfig, CompiledConfig};
+pub use config::{
+    Actions, ComponentNames, Config, ConfigError, ForwardRef, HocOrder, ImportStyle, Preset, RenderProps, Rule, Strategy, Wrapper,
+};
+pub use import_path::resolve_import_path;
+pub use matcher::{should_exclude, FileMatcher};
+
+/// Checks whether the JSX of a program targets React or one of the compatible
+/// `jsx_import_sources`, judging by its pragmas. Files targeting another
+/// runtime, e.g. `/** @jsxImportSource solid-js */`, must be left alone by
+/// both [observer_transform] and [action_transform].
+pub fn targets_react<C: Comments>(program: &Program, config: &Config, comments: C) -> bool {
+    let Program::Module(module) = program else {
+        return true;
+    };
+
+    let pragmas = JsxPragmas::of_module(module, &comments);
+    jsx::targets_react(module, &pragmas, &config.jsx_import_sources())
 }
 
-// Helper function to check if a path should be excluded
-pub fn should_exclude(file_path: &str, exclude_patterns: &[String]) -> bool {
-    if exclude_patterns.is_empty() {
-        return false;
+/// Applies the options of an `@observing-components` header comment on top of
+/// the configuration, reporting invalid options instead. The result is what
+/// [targets_react], [action_transform] and [observer_transform] should be
+/// given, so that the header applies to all of them.
+pub fn header_config<C: Comments>(program: &Program, config: &Config, comments: C) -> Config {
+    let mut config = config.clone();
+    let Some(leading) = comments.get_leading(program.span().lo) else {
+        return config;
+    };
+
+    for comment in leading {
+        match header::header_config(&comment.text, &config) {
+            Some(Ok(header_config)) => config = header_config,
+            Some(Err(err)) => HANDLER.with(|handler| {
+                handler.struct_span_err(comment.span, &err.to_string()).emit();
+            }),
+            None => {}
+        }
     }
 
-    let path = Path::new(file_path);
-    
-    // Create PathBuf from the full path
-    let path_buf = PathBuf::from(file_path);
-    
-    // Get just the file name for simpler matching
-    let file_name = path.file_name()
-        .and_then(|f| f.to_str())
-        .unwrap_or("");
-    
-    // Get the components of the path for more precise matching
-    let path_components: Vec<&str> = file_path.split(std::path::MAIN_SEPARATOR).collect();
-    
-    let mut builder = GlobSetBuilder::new();
-    
-    for pattern in exclude_patterns {
-        // Create a glob for each pattern and add to the set
-        match Glob::new(pattern) {
-            Ok(glob) => { builder.add(glob); },
-            Err(_) => { 
-                eprintln!("Invalid glob pattern: {}", pattern);
-                continue;
+    config
+}
+
+/// Wraps the components of a module in the observer, for programs that
+/// [targets_react]. The file name fills in the `{fileName}` placeholder of the
+/// options, and the comments are used to read the JSX pragmas.
+pub fn observer_transform<C: Comments>(config: Config, file_name: &str, comments: C) -> impl Pass {
+    let file_name = file_name.rsplit(['/', '\\']).next().unwrap_or_default();
+
+    fold_pass(ObserverTransform {
+        has_added_import: false,
+        has_wrapped: Cell::new(false),
+        config,
+        chain: Vec::new(),
+        react_memo: ReactMemo::default(),
+        ctx: Ctx::default(),
+        file_name: file_name.to_string(),
+        comments,
+    })
+}
+
+struct ObserverTransform<C: Comments> {
+    has_added_import: bool,
+    // Whether a component of the module was wrapped or had the hook injected,
+    // so the imports are needed
+    has_wrapped: Cell<bool>,
+    config: Config,
+    // The HOCs components are wrapped in, innermost first
+    chain: Vec<Wrapper>,
+    react_memo: ReactMemo,
+    ctx: Ctx,
+    file_name: String,
+    comments: C,
+}
+
+impl<C: Comments> ObserverTransform<C> {
+    // Reports the components left alone because of `skip_components`. Default
+    // exports are components whatever their name.
+    fn report_skipped_components(&self, module: &Module) {
+        let is_named_component = |ident: &Ident| self.config.component_names.is_match(&ident.sym);
+
+        for item in &module.body {
+            let skipped: Vec<(String, Span)> = match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                    match decl {
+                        Decl::Fn(fn_decl)
+                            if is_component_function(&self.ctx, &fn_decl.function)
+                                && is_named_component(&fn_decl.ident) =>
+                        {
+                            vec![(fn_decl.ident.sym.to_string(), fn_decl.ident.span)]
+                        }
+                        Decl::Var(var_decl) => var_decl
+                            .decls
+                            .iter()
+                            .filter_map(|decl| match (&decl.name, &decl.init) {
+                                (Pat::Ident(binding), Some(init))
+                                    if is_component_expr(&self.ctx, init) && is_named_component(&binding.id) =>
+                                {
+                                    Some((binding.id.sym.to_string(), binding.id.span))
+                                }
+                                _ => None,
+                            })
+                            .collect(),
+                        _ => Vec::new(),
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    span,
+                    decl: DefaultDecl::Fn(fn_expr),
+                    ..
+                })) if is_default_component_function(&self.ctx, fn_expr) => {
+                    vec![(default_name(fn_expr.ident.as_ref()), *span)]
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { span, expr }))
+                    if is_default_component_expr(&self.ctx, expr) =>
+                {
+                    vec![(default_export_name(expr), *span)]
+                }
+                _ => Vec::new(),
+            };
+
+            for (name, span) in skipped {
+                if names::is_skipped(&self.ctx, &name) {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_warn(
+                                span,
+                                &format!("`{}` is listed in `skip_components`, so it isn't made an observer", name),
+                            )
+                            .emit();
+                    });
+                }
             }
         }
     }
 
-    match builder.build() {
-        Ok(globset) => {
-            // Try to match against the full absolute path
-            if globset.is_match(file_path) {
-                return true;
-            }
-            
-            // Try to match against just the file name
-            if globset.is_match(file_name) {
-                return true;
+    // Whether a component can read observables, judging by the
+    // `reactive_sources` when set. Decisions are logged in debug builds.
+    fn is_reactive(&self, name: &str, reason: impl FnOnce(&ReactiveSources) -> Option<Reason>) -> bool {
+        let Some(reactive) = &self.ctx.reactive else {
+            return true;
+        };
+
+        let reason = reason(reactive);
+
+        match &reason {
+            Some(reason) => tracing::debug!(file = %self.file_name, component = name, "wrapping, as {}", reason),
+            None => tracing::debug!(
+                file = %self.file_name,
+                component = name,
+                "skipping, as it takes no props and uses no reactive source"
+            ),
+        }
+
+        reason.is_some()
+    }
+
+    fn is_reactive_fn(&self, name: &str, function: &Function) -> bool {
+        self.is_reactive(name, |reactive| reactive.reason_in_function(function))
+    }
+
+    fn is_reactive_expr(&self, name: &str, expr: &Expr) -> bool {
+        self.is_reactive(name, |reactive| reactive.reason_in_expr(&self.ctx, expr))
+    }
+
+    fn get_import_name(&self) -> String {
+        self.config.import_name.clone().unwrap_or_else(|| "observer".to_string())
+    }
+
+    fn is_observer(&self, wrapper: &Wrapper) -> bool {
+        wrapper.import_name == self.get_import_name() && wrapper.import_path == self.config.import_path
+    }
+
+    // The observer followed by the configured wrappers, unless they already
+    // place the observer somewhere
+    fn wrapper_chain(&self) -> Vec<Wrapper> {
+        if self.config.wrappers.iter().any(|wrapper| self.is_observer(wrapper)) {
+            return self.config.wrappers.clone();
+        }
+
+        let observer = Wrapper {
+            import_path: self.config.import_path.clone(),
+            import_name: self.get_import_name(),
+            import_style: self.config.import_style.unwrap_or_default(),
+            args: self.config.options.iter().cloned().collect(),
+        };
+
+        std::iter::once(observer)
+            .chain(self.config.wrappers.iter().cloned())
+            .collect()
+    }
+
+    // Returns the index in the chain of the wrapper called by the expression,
+    // looking only at the wrappers before `end`. Wrappers equivalent to the
+    // observer count as the observer.
+    fn chain_index(&self, expr: &Expr, end: usize) -> Option<usize> {
+        let name = wrapper_call_name(expr)?;
+        let Expr::Call(call_expr) = expr else {
+            return None;
+        };
+        if call_expr.args.first().map_or(true, |arg| arg.spread.is_some()) {
+            return None;
+        }
+
+        let is_equivalent = self
+            .config
+            .equivalent_wrappers
+            .iter()
+            .flatten()
+            .any(|equivalent| *equivalent == name);
+
+        self.chain[..end]
+            .iter()
+            .rposition(|wrapper| wrapper.import_name == name || (is_equivalent && self.is_observer(wrapper)))
+    }
+
+    // Returns how many wrappers of the chain are already applied to the
+    // expression, walking the nested calls from the outside in
+    fn applied_wrappers(&self, mut expr: &Expr) -> usize {
+        let mut end = self.chain.len();
+        let mut applied = 0;
+        while let Some(index) = self.chain_index(expr, end) {
+            let Expr::Call(call_expr) = expr else {
+                break;
+            };
+            applied += 1;
+            end = index;
+            expr = &call_expr.args[0].expr;
+        }
+
+        applied
+    }
+
+    // Adds the wrappers of the chain missing from a partially wrapped
+    // expression, e.g. `withErrorBoundary(fn)` becomes
+    // `withErrorBoundary(observer(fn))`. The applied calls keep their arguments.
+    fn complete_wrappers(&self, mut expr: Box<Expr>, component_name: &str) -> Expr {
+        let mut applied: Vec<Option<CallExpr>> = vec![None; self.chain.len()];
+        let mut end = self.chain.len();
+        while let Some(index) = self.chain_index(&expr, end) {
+            let Expr::Call(mut call_expr) = *expr else {
+                unreachable!("chain_index only matches calls");
+            };
+            expr = call_expr.args.remove(0).expr;
+            applied[index] = Some(call_expr);
+            end = index;
+        }
+
+        let expr = applied.into_iter().zip(&self.chain).fold(expr, |expr, (call_expr, wrapper)| {
+            Box::new(match call_expr {
+                Some(mut call_expr) => {
+                    call_expr.args.insert(0, ExprOrSpread { spread: None, expr });
+                    Expr::Call(call_expr)
+                }
+                None => self.wrapper_call(wrapper, expr, component_name),
+            })
+        });
+
+        *expr
+    }
+
+    // Creates e.g. `withErrorBoundary(observer(expr))`
+    fn wrap(&self, expr: Box<Expr>, component_name: &str) -> Expr {
+        let expr = self
+            .chain
+            .iter()
+            .fold(expr, |expr, wrapper| Box::new(self.wrapper_call(wrapper, expr, component_name)));
+
+        *expr
+    }
+
+    // Calls a single wrapper of the chain with the expression. The component
+    // name fills in the `{componentName}` placeholder.
+    fn wrapper_call(&self, wrapper: &Wrapper, expr: Box<Expr>, component_name: &str) -> Expr {
+        self.has_wrapped.set(true);
+        let placeholders = [("{componentName}", component_name), ("{fileName}", self.file_name.as_str())];
+
+        let args = std::iter::once(expr)
+            .chain(wrapper.args.iter().map(|arg| Box::new(json::json_expr(arg, &placeholders))))
+            .map(|expr| ExprOrSpread { spread: None, expr })
+            .collect();
+
+        Expr::Call(CallExpr {
+            span: Default::default(),
+            callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+                wrapper.import_name.as_str().into(),
+                Default::default(),
+                Default::default(),
+            )))),
+            args,
+            type_args: None,
+            ctxt: Default::default(),
+        })
+    }
+
+    fn is_hook_strategy(&self) -> bool {
+        self.config.strategy == Some(Strategy::Hook)
+    }
+
+    // Makes the component an observer, turning it into a variable when it is
+    // wrapped
+    fn transform_fn_decl(&self, mut fn_decl: FnDecl) -> Decl {
+        if self.is_hook_strategy() {
+            let component_name = fn_decl.ident.sym.clone();
+            self.inject_hook(&mut fn_decl.function, &component_name);
+            Decl::Fn(fn_decl)
+        } else {
+            Decl::Var(Box::new(self.wrap_fn_decl(fn_decl)))
+        }
+    }
+
+    // Injects the hook into the component function of the expression, e.g.
+    // `() => <div />` or the function in `forwardRef(() => <div />)`
+    fn inject_hook_in_expr(&self, expr: &mut Expr, component_name: &str) {
+        match expr {
+            Expr::Fn(fn_expr) => self.inject_hook(&mut fn_expr.function, component_name),
+            Expr::Arrow(arrow) => {
+                let body = match std::mem::replace(&mut *arrow.body, BlockStmtOrExpr::BlockStmt(Default::default())) {
+                    BlockStmtOrExpr::BlockStmt(block) => block.stmts,
+                    BlockStmtOrExpr::Expr(expr) => vec![Stmt::Return(ReturnStmt {
+                        span: Default::default(),
+                        arg: Some(expr),
+                    })],
+                };
+                *arrow.body = BlockStmtOrExpr::BlockStmt(BlockStmt {
+                    stmts: self.hook_body(body, component_name),
+                    ..Default::default()
+                });
             }
-            
-            // Check if any pattern matches the path components
-            // This helps with relative path patterns like "src/Test.tsx"
-            for pattern in exclude_patterns {
-                let pattern_components: Vec<&str> = pattern.split('/').collect();
-                
-                // Try to find the pattern components as a subsequence in the path
-                if path_components_match(&path_components, &pattern_components) {
-                    return true;
+            Expr::Paren(paren) => self.inject_hook_in_expr(&mut paren.expr, component_name),
+            Expr::Call(call_expr) => {
+                if let Some(arg) = call_expr.args.iter_mut().find(|arg| is_component_expr(&self.ctx, &arg.expr)) {
+                    self.inject_hook_in_expr(&mut arg.expr, component_name);
                 }
             }
-            
-            // Also try to match against path relative from project root
-            // This handles cases where exclude pattern is like "src/Test.tsx"
-            for i in 0..path_components.len() {
-                let potential_project_root = path_components[0..i].join(&std::path::MAIN_SEPARATOR.to_string());
-                let potential_relative_path = path_components[i..].join(&std::path::MAIN_SEPARATOR.to_string());
-                
-                if globset.is_match(&potential_relative_path) {
-                    return true;
+            _ => {}
+        }
+    }
+
+    fn inject_hook(&self, function: &mut Function, component_name: &str) {
+        if let Some(body) = &mut function.body {
+            body.stmts = self.hook_body(std::mem::take(&mut body.stmts), component_name);
+        }
+    }
+
+    // Turns the statements of a component into
+    // `const _t = useTracking(); try { ...body } finally { _t.end() }`
+    fn hook_body(&self, body: Vec<Stmt>, component_name: &str) -> Vec<Stmt> {
+        if body.first().is_some_and(|stmt| self.is_hook_call(stmt)) {
+            return body;
+        }
+        self.has_wrapped.set(true);
+
+        let placeholders = [("{componentName}", component_name), ("{fileName}", self.file_name.as_str())];
+        let tracking = Ident::new(
+            "_t".into(),
+            Default::default(),
+            SyntaxContext::empty().apply_mark(Mark::new()),
+        );
+
+        let hook_call = Expr::Call(CallExpr {
+            span: Default::default(),
+            callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+                self.get_import_name().into(),
+                Default::default(),
+                Default::default(),
+            )))),
+            args: self
+                .config
+                .options
+                .iter()
+                .map(|options| ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(json::json_expr(options, &placeholders)),
+                })
+                .collect(),
+            type_args: None,
+            ctxt: Default::default(),
+        });
+
+        let cleanup_call = Expr::Call(CallExpr {
+            span: Default::default(),
+            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                span: Default::default(),
+                obj: Box::new(Expr::Ident(tracking.clone())),
+                prop: MemberProp::Ident(IdentName::new(
+                    self.config.hook_cleanup.as_deref().unwrap_or("end").into(),
+                    Default::default(),
+                )),
+            }))),
+            args: Vec::new(),
+            type_args: None,
+            ctxt: Default::default(),
+        });
+
+        vec![
+            Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                span: Default::default(),
+                ctxt: Default::default(),
+                kind: VarDeclKind::Const,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: Default::default(),
+                    name: Pat::Ident(tracking.into()),
+                    init: Some(Box::new(hook_call)),
+                    definite: false,
+                }],
+            }))),
+            Stmt::Try(Box::new(TryStmt {
+                span: Default::default(),
+                block: BlockStmt {
+                    stmts: body,
+                    ..Default::default()
+                },
+                handler: None,
+                finalizer: Some(BlockStmt {
+                    stmts: vec![Stmt::Expr(ExprStmt {
+                        span: Default::default(),
+                        expr: Box::new(cleanup_call),
+                    })],
+                    ..Default::default()
+                }),
+            })),
+        ]
+    }
+
+    // Checks for `const _t = useTracking()`, so hooks are injected only once
+    fn is_hook_call(&self, stmt: &Stmt) -> bool {
+        let Stmt::Decl(Decl::Var(var_decl)) = stmt else {
+            return false;
+        };
+
+        var_decl.decls.iter().any(|decl| {
+            decl.init
+                .as_deref()
+                .and_then(wrapper_call_name)
+                .is_some_and(|name| name == self.get_import_name())
+        })
+    }
+
+    // Turns `function Home() {}` into `const Home = observer(function Home() {})`
+    fn wrap_fn_decl(&self, fn_decl: FnDecl) -> VarDecl {
+        let ident = fn_decl.ident.clone();
+        let span = fn_decl.function.span;
+        let fn_expr = Expr::Fn(FnExpr {
+            ident: Some(ident.clone()),
+            function: fn_decl.function,
+        });
+        let init = self.wrap(Box::new(fn_expr), &ident.sym);
+
+        VarDecl {
+            span,
+            ctxt: Default::default(),
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span,
+                name: Pat::Ident(BindingIdent {
+                    id: ident,
+                    type_ann: None,
+                }),
+                init: Some(Box::new(init)),
+                definite: false,
+            }],
+        }
+    }
+
+    // Wraps the initializers of the declared variables that are components
+    fn wrap_var_decl(&self, var_decl: &mut VarDecl) {
+        for decl in var_decl.decls.iter_mut() {
+            if let Some(init) = &mut decl.init {
+                let applied = self.applied_wrappers(init);
+                if applied == self.chain.len() {
+                    continue;
+                }
+
+                // Get variable name for component detection
+                let var_name = match &decl.name {
+                    Pat::Ident(binding_ident) => Some(binding_ident.id.sym.to_string()),
+                    _ => None
+                };
+
+                // Check if variable name starts with uppercase (component name)
+                let is_component = var_name.as_ref()
+                    .map(|name| is_component_name(&self.ctx, name))
+                    .unwrap_or(false);
+
+                let Some(var_name) = var_name.filter(|name| is_component && self.ctx.is_exported(name)) else {
+                    continue;
+                };
+
+                if is_component_expr(&self.ctx, init) && self.is_reactive_expr(&var_name, init) {
+                    if self.is_hook_strategy() {
+                        self.inject_hook_in_expr(init, &var_name);
+                    } else if applied > 0 {
+                        // Partially wrapped, e.g. `observer(fn)` when it also
+                        // needs `withErrorBoundary`, or the other way around
+                        **init = self.complete_wrappers(init.clone(), &var_name);
+                    } else {
+                        self.wrap_init(init, &var_name);
+                    }
                 }
             }
-            
-            false
-        },
-        Err(_) => {
-            eprintln!("Failed to build globset from patterns");
-            false
         }
     }
-}
 
-// Helper function to check if pattern components appear as a subsequence in path components
-fn path_components_match(path_components: &[&str], pattern_components: &[&str]) -> bool {
-    if pattern_components.is_empty() {
-        return true;
+    fn wrap_init(&self, init: &mut Box<Expr>, component_name: &str) {
+        // `memo` is replaced, as the observer memoizes the component already
+        if self.replace_memo(init, component_name) {
+            return;
+        }
+
+        // Handle both direct function expressions and wrapped functions
+        match &mut **init {
+            Expr::Arrow(_) | Expr::Fn(_) => {
+                **init = self.wrap(init.clone(), component_name);
+            },
+            // Handle cases like const Home = someWrapper(() => <div />)
+            Expr::Call(call_expr) => {
+                let jsx_arg = call_expr.args.iter().position(|arg| {
+                    match &*arg.expr {
+                        Expr::Arrow(_) | Expr::Fn(_) => is_component_expr(&self.ctx, &arg.expr),
+                        _ => false
+                    }
+                });
+
+                let Some(jsx_arg) = jsx_arg else {
+                    return;
+                };
+
+                let hoc_order = if is_forward_ref(call_expr) {
+                    match self.config.forward_ref {
+                        Some(ForwardRef::Skip) => return,
+                        Some(ForwardRef::Outer) => HocOrder::Outer,
+                        Some(ForwardRef::Inner) => HocOrder::Inner,
+                        None => self.config.hoc_order.unwrap_or_default(),
+                    }
+                } else {
+                    self.config.hoc_order.unwrap_or_default()
+                };
+
+                match hoc_order {
+                    // observer(someWrapper(() => <div />))
+                    HocOrder::Outer => {
+                        **init = self.wrap(init.clone(), component_name);
+                    },
+                    // someWrapper(observer(() => <div />))
+                    HocOrder::Inner => {
+                        let arg = &mut call_expr.args[jsx_arg].expr;
+                        **arg = self.wrap(arg.clone(), component_name);
+                    },
+                }
+            },
+            _ => {}
+        }
     }
-    
-    // Look for the pattern components in sequence within the path components
-    let mut path_idx = 0;
-    let mut pattern_idx = 0;
-    
-    while path_idx < path_components.len() && pattern_idx < pattern_components.len() {
-        if path_components[path_idx].to_lowercase() == pattern_components[pattern_idx].to_lowercase() {
-            pattern_idx += 1;
-            if pattern_idx == pattern_components.len() {
-                return true;
+
+    // Replaces `memo(fn, areEqual)` of `react` with the observer, keeping the
+    // comparator when the observer takes one and reporting it's dropped
+    // otherwise. Returns whether the expression was a `memo` call.
+    fn replace_memo(&self, expr: &mut Box<Expr>, component_name: &str) -> bool {
+        let Expr::Call(call_expr) = &mut **expr else {
+            return false;
+        };
+        if !self.react_memo.is_memo_call(call_expr) || !is_component_expr(&self.ctx, &call_expr.args[0].expr) {
+            return false;
+        }
+
+        let mut args = std::mem::take(&mut call_expr.args).into_iter();
+        let component = args.next().unwrap().expr;
+        let comparator = args.next();
+
+        let mut wrapped = self.wrap(component, component_name);
+
+        if let Some(comparator) = comparator {
+            if self.config.observer_comparator {
+                // Like `memo`, the comparator comes right after the component,
+                // followed by the options
+                if let Some(observer) = self.find_observer_call(&mut wrapped) {
+                    observer.args.insert(1, comparator);
+                }
+            } else {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_warn(
+                            comparator.expr.span(),
+                            &format!(
+                                "the `memo` comparator of `{}` is dropped, as `{}` takes none; set \
+                                 `observer_comparator` if it does",
+                                component_name,
+                                self.get_import_name()
+                            ),
+                        )
+                        .emit();
+                });
             }
         }
-        path_idx += 1;
+
+        **expr = wrapped;
+        true
+    }
+
+    // Finds the observer call among the wrappers of a component, e.g.
+    // `observer(fn)` in `withErrorBoundary(observer(fn))`
+    fn find_observer_call<'a>(&self, expr: &'a mut Expr) -> Option<&'a mut CallExpr> {
+        let Expr::Call(call_expr) = expr else {
+            return None;
+        };
+
+        let is_observer = matches!(
+            &call_expr.callee,
+            Callee::Expr(callee) if matches!(&**callee, Expr::Ident(ident) if ident.sym == *self.get_import_name())
+        );
+        if is_observer {
+            return Some(call_expr);
+        }
+
+        let first = call_expr.args.first_mut()?;
+        self.find_observer_call(&mut first.expr)
+    }
+
+    // Renders the render props of the module through `<Observer>`, importing
+    // it when needed
+    fn observe_render_props(&self, module: &mut Module, render_props: &RenderProps) {
+        let mut observer = render_props::RenderPropsObserver::new(&self.ctx, render_props);
+        module.visit_mut_with(&mut observer);
+
+        if !observer.has_observer {
+            return;
+        }
+
+        let wrapper = Wrapper {
+            import_path: render_props
+                .import_path
+                .clone()
+                .unwrap_or_else(|| self.config.import_path.clone()),
+            import_name: render_props.observer_name().to_string(),
+            import_style: ImportStyle::Named,
+            args: Vec::new(),
+        };
+
+        if !has_import(module, &wrapper) {
+            module.body.insert(0, create_import(&wrapper));
+        }
     }
-    
-    false
 }
 
-fn default_import_name() -> String {
-    "observer".to_string()
+pub(crate) fn create_import(wrapper: &Wrapper) -> ModuleItem {
+    let local = Ident::new(wrapper.import_name.as_str().into(), Default::default(), Default::default());
+    let specifier = match wrapper.import_style {
+        ImportStyle::Named => ImportSpecifier::Named(ImportNamedSpecifier {
+            span: Default::default(),
+            local,
+            imported: None,
+            is_type_only: false,
+        }),
+        ImportStyle::Default => ImportSpecifier::Default(ImportDefaultSpecifier {
+            span: Default::default(),
+            local,
+        }),
+    };
+
+    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+        span: Default::default(),
+        specifiers: vec![specifier],
+        src: Box::new(Str {
+            span: Default::default(),
+            value: wrapper.import_path.as_str().into(),
+            raw: None,
+        }),
+        type_only: false,
+        with: None,
+        phase: ImportPhase::Evaluation,
+    }))
 }
 
-pub fn observer_transform(config: Config) -> impl Pass {
-    fold_pass(ObserverTransform {
-        has_added_import: false,
-        config,
+// Returns whether the module already imports the wrapper under the name the
+// wrapper is called by
+pub(crate) fn has_import(module: &Module, wrapper: &Wrapper) -> bool {
+    module.body.iter().any(|item| {
+        let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = item else {
+            return false;
+        };
+
+        import_decl.specifiers.iter().any(|spec| match spec {
+            ImportSpecifier::Named(named) => {
+                let imported = if let Some(imported) = &named.imported {
+                    match imported {
+                        ModuleExportName::Ident(ident) => ident.sym.to_string(),
+                        ModuleExportName::Str(s) => s.value.to_string(),
+                    }
+                } else {
+                    named.local.sym.to_string()
+                };
+                imported == wrapper.import_name && named.local.sym == *wrapper.import_name
+            },
+            ImportSpecifier::Default(default) => {
+                wrapper.import_style == ImportStyle::Default
+                    && import_decl.src.value == *wrapper.import_path
+                    && default.local.sym == *wrapper.import_name
+            },
+            _ => false,
+        })
     })
 }
 
-struct ObserverTransform {
-    has_added_import: bool,
-    config: Config,
-}
+// Checks for `forwardRef(...)` and `React.forwardRef(...)`
+fn is_forward_ref(call_expr: &CallExpr) -> bool {
+    let Callee::Expr(callee) = &call_expr.callee else {
+        return false;
+    };
 
-impl ObserverTransform {
-    fn get_import_name(&self) -> String {
-        self.config.import_name.clone().unwrap_or_else(|| "observer".to_string())
+    match &**callee {
+        Expr::Ident(id) => id.sym == "forwardRef",
+        Expr::Member(member) => member.prop.as_ident().is_some_and(|prop| prop.sym == "forwardRef"),
+        _ => false,
     }
 }
 
-// Updated function to check if property key has uppercase first letter
-fn is_component_name(name: &str) -> bool {
-    if let Some(first_char) = name.chars().next() {
-        first_char.is_uppercase()
-    } else {
-        false
+// Removed get_computed_property_name helper function as it's no longer needed
+
+// Checks whether the function of a component name renders, judging by its JSX
+// or, with `detect_hook_components`, by the hooks it calls
+pub(crate) fn is_component_function(ctx: &Ctx, function: &Function) -> bool {
+    contains_jsx_in_function(ctx, function) || hooks::uses_hooks_in_function(ctx, function)
+}
+
+// Checks whether a default exported function is a component. Functions named
+// like hooks, e.g. `export default function useUser()`, call hooks too but
+// aren't.
+pub(crate) fn is_default_component_function(ctx: &Ctx, fn_expr: &FnExpr) -> bool {
+    contains_jsx_in_function(ctx, &fn_expr.function)
+        || (hooks::uses_hooks_in_function(ctx, &fn_expr.function)
+            && !fn_expr.ident.as_ref().is_some_and(|ident| hooks::is_hook_name(&ident.sym)))
+}
+
+// Like `is_default_component_function`, for any default exported expression
+pub(crate) fn is_default_component_expr(ctx: &Ctx, expr: &Expr) -> bool {
+    match expr {
+        Expr::Fn(fn_expr) => is_default_component_function(ctx, fn_expr),
+        _ => is_component_expr(ctx, expr),
     }
 }
 
-// Removed get_computed_property_name helper function as it's no longer needed
+pub(crate) fn is_component_expr(ctx: &Ctx, expr: &Expr) -> bool {
+    !names::is_lazy_call(ctx, expr) && (contains_jsx_in_expr(ctx, expr) || hooks::uses_hooks_in_expr(ctx, expr))
+}
 
-fn contains_jsx_in_expr(expr: &Expr) -> bool {
+pub(crate) fn contains_jsx_in_expr(ctx: &Ctx, expr: &Expr) -> bool {
     match expr {
         Expr::JSXElement(_) | Expr::JSXFragment(_) => true,
-        Expr::Paren(e) => contains_jsx_in_expr(&e.expr),
-        Expr::Fn(f) => contains_jsx_in_function(&f.function),
+        Expr::Paren(e) => contains_jsx_in_expr(ctx, &e.expr),
+        Expr::Fn(f) => contains_jsx_in_function(ctx, &f.function),
         Expr::Arrow(arrow) => {
             if let BlockStmtOrExpr::BlockStmt(block) = &*arrow.body {
-                contains_jsx_in_block(block)
+                contains_jsx_in_block(ctx, block)
             } else if let BlockStmtOrExpr::Expr(expr) = &*arrow.body {
-                contains_jsx_in_expr(expr)
+                contains_jsx_in_expr(ctx, expr)
             } else {
                 false
             }
         },
-        // Check if Call expressions' arguments contain JSX
-        Expr::Call(call_expr) => call_expr.args.iter().any(|arg| contains_jsx_in_expr(&arg.expr)),
+        // Check if Call expressions are compiled JSX or their arguments contain JSX
+        Expr::Call(call_expr) => {
+            jsx::is_jsx_call(ctx, call_expr) || call_expr.args.iter().any(|arg| contains_jsx_in_expr(ctx, &arg.expr))
+        }
         // Removed Object properties JSX checking
         _ => false
     }
 }
 
-fn contains_jsx_in_function(function: &Function) -> bool {
+pub(crate) fn contains_jsx_in_function(ctx: &Ctx, function: &Function) -> bool {
     if let Some(body) = &function.body {
-        contains_jsx_in_block(body)
+        contains_jsx_in_block(ctx, body)
     } else {
         false
     }
 }
 
-fn contains_jsx_in_block(block: &BlockStmt) -> bool {
-    block.stmts.iter().any(|stmt| contains_jsx_in_stmt(stmt))
+fn contains_jsx_in_block(ctx: &Ctx, block: &BlockStmt) -> bool {
+    block.stmts.iter().any(|stmt| contains_jsx_in_stmt(ctx, stmt))
 }
 
-fn contains_jsx_in_stmt(stmt: &Stmt) -> bool {
+fn contains_jsx_in_stmt(ctx: &Ctx, stmt: &Stmt) -> bool {
     match stmt {
-        Stmt::Decl(Decl::Fn(fn_decl)) => contains_jsx_in_function(&fn_decl.function), // NEW: check function declarations
+        Stmt::Decl(Decl::Fn(fn_decl)) => contains_jsx_in_function(ctx, &fn_decl.function), // NEW: check function declarations
         Stmt::Return(ret) => {
             if let Some(expr) = &ret.arg {
-                contains_jsx_in_expr(expr)
+                contains_jsx_in_expr(ctx, expr)
             } else {
                 false
             }
         },
-        Stmt::Expr(expr) => contains_jsx_in_expr(&expr.expr),
-        Stmt::Block(block) => contains_jsx_in_block(block),
+        Stmt::Expr(expr) => contains_jsx_in_expr(ctx, &expr.expr),
+        Stmt::Block(block) => contains_jsx_in_block(ctx, block),
         Stmt::Decl(Decl::Var(var_decl)) => var_decl.decls.iter().any(|decl| {
             if let Some(init) = &decl.init {
-                contains_jsx_in_expr(init)
+                contains_jsx_in_expr(ctx, init)
             } else {
                 false
             }
@@ -200,51 +839,25 @@ fn contains_jsx_in_stmt(stmt: &Stmt) -> bool {
     }
 }
 
-fn contains_jsx_in_module(module: &Module) -> bool {
-    module.body.iter().any(|item| match item {
-        ModuleItem::Stmt(stmt) => match stmt {
-            // Add explicit check for variable declarations in module statements
-            Stmt::Decl(Decl::Var(var_decl)) => var_decl.decls.iter().any(|decl| {
-                if let Some(init) = &decl.init {
-                    contains_jsx_in_expr(init)
-                } else {
-                    false
-                }
-            }),
-            _ => contains_jsx_in_stmt(stmt),
-        },
-        ModuleItem::ModuleDecl(decl) => match decl {
-            ModuleDecl::ExportDefaultExpr(export) => contains_jsx_in_expr(&export.expr),
-            ModuleDecl::ExportDecl(export_decl) => match &export_decl.decl {
-                Decl::Fn(fn_decl) => contains_jsx_in_function(&fn_decl.function),
-                Decl::Var(var_decl) => var_decl.decls.iter().any(|decl| {
-                    if let Some(init) = &decl.init {
-                        // Check arrow functions in variable declarations
-                        contains_jsx_in_expr(init)
-                    } else {
-                        false
-                    }
-                }),
-                _ => false,
-            },
-            ModuleDecl::ExportDefaultDecl(export_decl) => {
-                if let swc_ecma_ast::DefaultDecl::Fn(f) = &export_decl.decl {
-                    contains_jsx_in_function(&f.function)
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        },
-    })
+// Returns the name of the function called by `expr`, e.g. `observer` for
+// `observer(fn)`
+fn wrapper_call_name(expr: &Expr) -> Option<&str> {
+    let Expr::Call(call_expr) = expr else {
+        return None;
+    };
+    let Callee::Expr(callee) = &call_expr.callee else {
+        return None;
+    };
+
+    callee.as_ident().map(|id| &*id.sym)
 }
 
 // NEW: Helper to check if an expression is already wrapped
-fn is_already_wrapped(expr: &Expr, observer_name: &str) -> bool {
+fn is_already_wrapped(expr: &Expr, wrapper_names: &[String]) -> bool {
     if let Expr::Call(call_expr) = expr {
         if let Callee::Expr(boxed) = &call_expr.callee {
             if let Expr::Ident(id) = &**boxed {
-                return id.sym.to_string() == observer_name;
+                return wrapper_names.iter().any(|name| id.sym == *name);
             }
         }
     }
@@ -252,14 +865,14 @@ fn is_already_wrapped(expr: &Expr, observer_name: &str) -> bool {
 }
 
 // NEW: Update helper to check for wrapped functions in variable declarations as well.
-fn module_contains_wrapped_function(module: &Module, observer_name: &str) -> bool {
+fn module_contains_wrapped_function(module: &Module, wrapper_names: &[String]) -> bool {
     module.body.iter().any(|item| match item {
         // Check top-level expression statements.
         ModuleItem::Stmt(Stmt::Expr(expr_stmt)) => {
             if let Expr::Call(call_expr) = &*expr_stmt.expr {
                 if let Callee::Expr(boxed) = &call_expr.callee {
                     if let Expr::Ident(id) = &**boxed {
-                        return id.sym.to_string() == observer_name;
+                        return wrapper_names.iter().any(|name| id.sym == *name);
                     }
                 }
             }
@@ -269,7 +882,7 @@ fn module_contains_wrapped_function(module: &Module, observer_name: &str) -> boo
         ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => {
             var_decl.decls.iter().any(|decl| {
                 if let Some(init) = &decl.init {
-                    is_already_wrapped(init, observer_name)
+                    is_already_wrapped(init, wrapper_names)
                 } else {
                     false
                 }
@@ -280,7 +893,7 @@ fn module_contains_wrapped_function(module: &Module, observer_name: &str) -> boo
             Decl::Var(var_decl) => {
                 var_decl.decls.iter().any(|decl| {
                     if let Some(init) = &decl.init {
-                        is_already_wrapped(init, observer_name)
+                        is_already_wrapped(init, wrapper_names)
                     } else {
                         false
                     }
@@ -292,365 +905,163 @@ fn module_contains_wrapped_function(module: &Module, observer_name: &str) -> boo
     })
 }
 
-impl Fold for ObserverTransform {
+impl<C: Comments> Fold for ObserverTransform<C> {
     noop_fold_type!();
 
     fn fold_module(&mut self, mut module: Module) -> Module {
-        // ...existing code...
-        let should_add_import = contains_jsx_in_module(&module);
-        let observer_name = self.get_import_name();
+        let pragmas = JsxPragmas::of_module(&module, &self.comments);
+        self.ctx = Ctx::new(&module, &self.config, &pragmas);
+        self.chain = self.wrapper_chain();
 
-        // NEW: Do not add an import if an already wrapped function is identified.
-        if module_contains_wrapped_function(&module, &observer_name) {
-            self.has_added_import = true;
+        self.react_memo = ReactMemo::new(&module);
+
+        if self.config.extract_map_items {
+            let lifted = map_items::extract_map_items(&self.ctx, &mut module, self.ctx.exported.as_ref());
+            // Items of exported components are wrapped along with them
+            if let Some(exported) = &mut self.ctx.exported {
+                exported.extend(lifted);
+            }
         }
 
-        if should_add_import && !self.has_added_import {
-            // ...existing import logic...
-            let mut observer_alias = observer_name.clone(); // default alias
-            let found_alias = module.body.iter().filter_map(|item| {
-                if let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = item {
-                    for spec in &import_decl.specifiers {
-                        if let ImportSpecifier::Named(named) = spec {
-                            let imported = if let Some(imported) = &named.imported {
-                                match imported {
-                                    ModuleExportName::Ident(ident) => ident.sym.to_string(),
-                                    ModuleExportName::Str(s) => s.value.to_string(),
-                                }
-                            } else {
-                                named.local.sym.to_string()
-                            };
-                            if imported == observer_name {
-                                return Some(named.local.sym.to_string());
-                            }
-                        }
-                    }
-                }
-                None
-            }).next();
+        self.report_skipped_components(&module);
 
-            if let Some(alias) = found_alias {
-                observer_alias = alias;
-                self.has_added_import = true;
-            } else {
-                let import_path = self.config.import_path.clone();
-                let import = ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                    span: Default::default(),
-                    specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
-                        span: Default::default(),
-                        local: Ident::new(observer_name.clone().into(), Default::default(), Default::default()),
-                        imported: None,
-                        is_type_only: false,
-                    })],
-                    src: Box::new(Str {
-                        span: Default::default(),
-                        value: import_path.into(),
-                        raw: None,
-                    }),
-                    type_only: false,
-                    with: None,
-                    phase: ImportPhase::Evaluation,
-                }));
-                module.body.insert(0, import);
-                self.has_added_import = true;
-            }
-            // ...existing code...
+        // NEW: Do not add an import if an already wrapped function is identified.
+        // Equivalent wrappers don't count, as they don't import the observer.
+        if module_contains_wrapped_function(&module, &[self.get_import_name()]) {
+            self.has_added_import = true;
         }
 
+        self.has_wrapped.set(false);
         let transformed_body = module.body.into_iter().map(|item| {
-            // ...existing transformation code...
             match item {
-                // ...existing code...
                 ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => {
-                    if contains_jsx_in_function(&fn_decl.function) && is_component_name(&fn_decl.ident.sym.to_string()) {
-                        let ident = fn_decl.ident.clone();
-                        let fn_expr = Expr::Fn(FnExpr {
-                            ident: Some(ident.clone()),
-                            function: fn_decl.function.clone(),
-                        });
-                        let wrapped_fn_expr = Expr::Call(CallExpr {
-                            span: Default::default(),
-                            callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                                observer_name.clone().into(),
-                                Default::default(),
-                                Default::default(),
-                            )))),
-                            args: vec![ExprOrSpread {
-                                spread: None,
-                                expr: Box::new(fn_expr),
-                            }],
-                            type_args: None,
-                            ctxt: Default::default(),
-                        });
-                        let var_decl = VarDecl {
-                            span: fn_decl.function.span,
-                            ctxt: Default::default(),
-                            kind: VarDeclKind::Const,
-                            declare: false,
-                            decls: vec![VarDeclarator {
-                                span: fn_decl.function.span,
-                                name: Pat::Ident(BindingIdent {
-                                    id: ident,
-                                    type_ann: None,
-                                }),
-                                init: Some(Box::new(wrapped_fn_expr)),
-                                definite: false,
-                            }],
-                        };
-                        ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(var_decl))))
+                    if is_component_function(&self.ctx, &fn_decl.function)
+                        && is_component_name(&self.ctx, &fn_decl.ident.sym)
+                        && self.ctx.is_exported(&fn_decl.ident.sym)
+                        && self.is_reactive_fn(&fn_decl.ident.sym, &fn_decl.function)
+                    {
+                        ModuleItem::Stmt(Stmt::Decl(self.transform_fn_decl(fn_decl)))
                     } else {
                         ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl)))
                     }
                 },
                 ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export))
-                    if contains_jsx_in_expr(&export.expr) =>
+                    if is_default_component_expr(&self.ctx, &export.expr)
+                        && !names::is_skipped(&self.ctx, &default_export_name(&export.expr))
+                        && self.is_reactive_expr(&default_export_name(&export.expr), &export.expr) =>
                 {
-                    let wrapped_expr = Expr::Call(CallExpr {
-                        span: Default::default(),
-                        callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                            observer_name.clone().into(),
-                            Default::default(),
-                            Default::default(),
-                        )))),
-                        args: vec![ExprOrSpread {
-                            spread: None,
-                            expr: export.expr,
-                        }],
-                        type_args: None,
-                        ctxt: Default::default(),
-                    });
-                    
+                    let applied = self.applied_wrappers(&export.expr);
+                    let component_name = default_export_name(&export.expr);
+                    let expr = if self.is_hook_strategy() {
+                        let mut expr = export.expr;
+                        self.inject_hook_in_expr(&mut expr, &component_name);
+                        *expr
+                    } else {
+                        let mut expr = export.expr;
+                        if applied > 0 {
+                            self.complete_wrappers(expr, &component_name)
+                        } else if self.replace_memo(&mut expr, &component_name) {
+                            *expr
+                        } else {
+                            self.wrap(expr, &component_name)
+                        }
+                    };
                     ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
                         span: export.span,
-                        expr: Box::new(wrapped_expr),
+                        expr: Box::new(expr),
                     }))
                 },
                 ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(mut export_decl)) => {
-                    match &mut export_decl.decl {
-                        Decl::Fn(fn_decl) => {
-                            if contains_jsx_in_function(&fn_decl.function) && is_component_name(&fn_decl.ident.sym.to_string()) {
-                                let ident = fn_decl.ident.clone();
-                                let fn_expr = Expr::Fn(FnExpr {
-                                    ident: Some(ident.clone()),
-                                    function: fn_decl.function.clone(),
-                                });
-                                let wrapped_fn_expr = Expr::Call(CallExpr {
-                                    span: Default::default(),
-                                    callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                                        observer_name.clone().into(),
-                                        Default::default(),
-                                        Default::default(),
-                                    )))),
-                                    args: vec![ExprOrSpread {
-                                        spread: None,
-                                        expr: Box::new(fn_expr),
-                                    }],
-                                    type_args: None,
-                                    ctxt: Default::default(),
-                                });
-                                let var_decl = VarDecl {
-                                    span: fn_decl.function.span,
-                                    ctxt: Default::default(),
-                                    kind: VarDeclKind::Const,
-                                    declare: false,
-                                    decls: vec![VarDeclarator {
-                                        span: fn_decl.function.span,
-                                        name: Pat::Ident(BindingIdent {
-                                            id: ident,
-                                            type_ann: None,
-                                        }),
-                                        init: Some(Box::new(wrapped_fn_expr)),
-                                        definite: false,
-                                    }],
-                                };
-                                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
-                                    span: export_decl.span,
-                                    decl: Decl::Var(Box::new(var_decl)),
-                                }))
-                            } else {
-                                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl))
-                            }
+                    match export_decl.decl {
+                        Decl::Fn(fn_decl)
+                            if is_component_function(&self.ctx, &fn_decl.function)
+                                && is_component_name(&self.ctx, &fn_decl.ident.sym)
+                                && self.is_reactive_fn(&fn_decl.ident.sym, &fn_decl.function) =>
+                        {
+                            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                                span: export_decl.span,
+                                decl: self.transform_fn_decl(fn_decl),
+                            }))
                         },
-                        Decl::Var(var_decl) => {
-                            for decl in var_decl.decls.iter_mut() {
-                                if let Some(init) = &mut decl.init {
-                                    if is_already_wrapped(&*init, &observer_name) {
-                                        continue;
-                                    }
-                                    
-                                    // Get variable name for component detection
-                                    let var_name = match &decl.name {
-                                        Pat::Ident(binding_ident) => Some(binding_ident.id.sym.to_string()),
-                                        _ => None
-                                    };
-                                    
-                                    // Check if variable name starts with uppercase (component name)
-                                    let is_component = var_name.as_ref()
-                                        .map(|name| is_component_name(name))
-                                        .unwrap_or(false);
-                                    
-                                    if is_component && contains_jsx_in_expr(&*init) {
-                                        // Handle both direct function expressions and wrapped functions
-                                        match &**init {
-                                            Expr::Arrow(_) | Expr::Fn(_) => {
-                                                let wrapped = Expr::Call(CallExpr {
-                                                    span: Default::default(),
-                                                    callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                                                        observer_name.clone().into(),
-                                                        Default::default(),
-                                                        Default::default(),
-                                                    )))),
-                                                    args: vec![ExprOrSpread {
-                                                        spread: None,
-                                                        expr: init.clone(),
-                                                    }],
-                                                    type_args: None,
-                                                    ctxt: Default::default(),
-                                                });
-                                                *init = Box::new(wrapped);
-                                            },
-                                            // Handle cases like const Home = someWrapper(() => <div />)
-                                            Expr::Call(call_expr) => {
-                                                let has_jsx_arg = call_expr.args.iter().any(|arg| {
-                                                    match &*arg.expr {
-                                                        Expr::Arrow(_) | Expr::Fn(_) => contains_jsx_in_expr(&arg.expr),
-                                                        _ => false
-                                                    }
-                                                });
-                                                
-                                                if has_jsx_arg {
-                                                    let wrapped = Expr::Call(CallExpr {
-                                                        span: Default::default(),
-                                                        callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                                                            observer_name.clone().into(),
-                                                            Default::default(),
-                                                            Default::default(),
-                                                        )))),
-                                                        args: vec![ExprOrSpread {
-                                                            spread: None,
-                                                            expr: init.clone(),
-                                                        }],
-                                                        type_args: None,
-                                                        ctxt: Default::default(),
-                                                    });
-                                                    *init = Box::new(wrapped);
-                                                }
-                                            },
-                                            _ => {}
-                                        }
-                                    }
-                                }
-                            }
+                        Decl::Var(ref mut var_decl) => {
+                            self.wrap_var_decl(var_decl);
                             ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl))
                         },
                         _ => ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl))
                     }
                 },
                 ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export_decl)) => {
-                    if let swc_ecma_ast::DefaultDecl::Fn(ref f) = export_decl.decl {
-                        if contains_jsx_in_function(&f.function) {
-                            let wrapped_expr = Expr::Call(CallExpr {
-                                span: Default::default(),
-                                callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                                    observer_name.clone().into(),
-                                    Default::default(),
-                                    Default::default(),
-                                )))),
-                                args: vec![ExprOrSpread {
-                                    spread: None,
-                                    expr: Box::new(Expr::Fn(f.clone())),
-                                }],
-                                type_args: None,
-                                ctxt: Default::default(),
-                            });
+                    match export_decl.decl {
+                        DefaultDecl::Fn(f)
+                            if names::is_skipped(&self.ctx, &default_name(f.ident.as_ref()))
+                                || (is_default_component_function(&self.ctx, &f)
+                                    && !self.is_reactive_fn(&default_name(f.ident.as_ref()), &f.function)) =>
+                        {
+                            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                                decl: DefaultDecl::Fn(f),
+                                ..export_decl
+                            }))
+                        },
+                        DefaultDecl::Fn(mut f)
+                            if self.is_hook_strategy() && is_default_component_function(&self.ctx, &f) =>
+                        {
+                            let component_name = default_export_name(&Expr::Fn(f.clone()));
+                            self.inject_hook(&mut f.function, &component_name);
+                            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                                decl: DefaultDecl::Fn(f),
+                                ..export_decl
+                            }))
+                        },
+                        DefaultDecl::Fn(f) if is_default_component_function(&self.ctx, &f) => {
+                            let expr = Expr::Fn(f);
+                            let component_name = default_export_name(&expr);
                             ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
                                 span: export_decl.span,
-                                expr: Box::new(wrapped_expr),
+                                expr: Box::new(self.wrap(Box::new(expr), &component_name)),
                             }))
-                        } else {
-                            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export_decl))
-                        }
-                    } else {
-                        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export_decl))
+                        },
+                        decl => ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                            decl,
+                            ..export_decl
+                        })),
                     }
                 },
                 // Fix non-exported variable declarations
                 ModuleItem::Stmt(Stmt::Decl(Decl::Var(mut var_decl))) => {
-                    for decl in var_decl.decls.iter_mut() {
-                        if let Some(init) = &mut decl.init {
-                            if is_already_wrapped(&*init, &observer_name) {
-                                continue;
-                            }
-                            
-                            // Get variable name for component detection
-                            let var_name = match &decl.name {
-                                Pat::Ident(binding_ident) => Some(binding_ident.id.sym.to_string()),
-                                _ => None
-                            };
-                            
-                            // Check if variable name starts with uppercase (component name)
-                            let is_component = var_name.as_ref()
-                                .map(|name| is_component_name(name))
-                                .unwrap_or(false);
-                            
-                            if is_component && contains_jsx_in_expr(&*init) {
-                                // Handle both direct function expressions and wrapped functions
-                                match &**init {
-                                    Expr::Arrow(_) | Expr::Fn(_) => {
-                                        let wrapped = Expr::Call(CallExpr {
-                                            span: Default::default(),
-                                            callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                                                observer_name.clone().into(),
-                                                Default::default(),
-                                                Default::default(),
-                                            )))),
-                                            args: vec![ExprOrSpread {
-                                                spread: None,
-                                                expr: init.clone(),
-                                            }],
-                                            type_args: None,
-                                            ctxt: Default::default(),
-                                        });
-                                        *init = Box::new(wrapped);
-                                    },
-                                    // Handle cases like const Home = someWrapper(() => <div />)
-                                    Expr::Call(call_expr) => {
-                                        let has_jsx_arg = call_expr.args.iter().any(|arg| {
-                                            match &*arg.expr {
-                                                Expr::Arrow(_) | Expr::Fn(_) => contains_jsx_in_expr(&arg.expr),
-                                                _ => false
-                                            }
-                                        });
-                                        
-                                        if has_jsx_arg {
-                                            let wrapped = Expr::Call(CallExpr {
-                                                span: Default::default(),
-                                                callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                                                    observer_name.clone().into(),
-                                                    Default::default(),
-                                                    Default::default(),
-                                                )))),
-                                                args: vec![ExprOrSpread {
-                                                    spread: None,
-                                                    expr: init.clone(),
-                                                }],
-                                                type_args: None,
-                                                ctxt: Default::default(),
-                                            });
-                                            *init = Box::new(wrapped);
-                                        }
-                                    },
-                                    _ => {}
-                                }
-                            }
-                        }
-                    }
+                    self.wrap_var_decl(&mut var_decl);
                     ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl)))
                 },
                 item => item,
             }
         }).collect();
 
-        Module { body: transformed_body, ..module }
+        let mut module = Module { body: transformed_body, ..module };
+
+        // Only once the components are transformed is it known whether any was
+        // wrapped, e.g. none is when all are skipped, private or static
+        if self.has_wrapped.get() {
+            let mut imports = Vec::new();
+
+            for wrapper in &self.chain {
+                if self.is_observer(wrapper) {
+                    if !self.has_added_import {
+                        if !has_import(&module, wrapper) {
+                            imports.push(create_import(wrapper));
+                        }
+                        self.has_added_import = true;
+                    }
+                } else if !has_import(&module, wrapper) {
+                    imports.push(create_import(wrapper));
+                }
+            }
+
+            module.body.splice(0..0, imports);
+        }
+
+        if let Some(render_props) = &self.config.render_props {
+            self.observe_render_props(&mut module, render_props);
+        }
+
+        module
     }
-}
\ No newline at end of file
+}
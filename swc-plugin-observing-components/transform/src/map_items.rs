@@ -0,0 +1,400 @@
+//! Lifting `.map` item renderers into components
+
+use std::collections::HashSet;
+
+use swc_atoms::Atom;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+
+use crate::{components::components, context::Ctx, contains_jsx_in_expr};
+
+// Props React handles itself, so they can't pass a value to the item
+const RESERVED_PROPS: &[&str] = &["key", "ref"];
+
+/// Lifts inline `.map` callbacks rendering JSX in components into module level
+/// components, so every item is an observer of its own:
+///
+/// ```js
+/// const Todos = ({ todos, prefix }) => <ul>{todos.map(todo => <li key={todo.id}>{prefix}{todo.title}</li>)}</ul>;
+/// // becomes
+/// function TodosItem({ todo, prefix }) {
+///     return <li>{prefix}{todo.title}</li>;
+/// }
+/// const Todos = ({ todos, prefix }) => <ul>{todos.map(todo => <TodosItem key={todo.id} todo={todo} prefix={prefix} />)}</ul>;
+/// ```
+///
+/// Variables of the component used by the callback are passed as props. Only
+/// callbacks returning JSX directly, with plain parameters and no `this`,
+/// `arguments`, assignments or nested functions other than arrow functions
+/// returning an expression are lifted, as anything else might not behave the
+/// same in a component of its own.
+///
+/// The lifted functions are wrapped like any other component afterwards. With
+/// `exported`, only the items of exported components are lifted. Returns the
+/// names of the lifted functions.
+pub(crate) fn extract_map_items(ctx: &Ctx, module: &mut Module, exported: Option<&HashSet<Atom>>) -> Vec<Atom> {
+    let mut names = module_names(module);
+    let mut lifted_names = Vec::new();
+    let body = std::mem::take(&mut module.body);
+
+    for mut item in body {
+        let is_default_export = matches!(
+            item,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(_) | ModuleDecl::ExportDefaultExpr(_))
+        );
+
+        let mut lifted = Vec::new();
+        extract_in_item(ctx, &mut item, &mut names, &mut lifted, |name| {
+            is_default_export || exported.map_or(true, |exported| exported.contains(&Atom::from(name)))
+        });
+
+        lifted_names.extend(lifted.iter().map(|fn_decl| fn_decl.ident.sym.clone()));
+        module.body.extend(lifted.into_iter().map(|fn_decl| ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl)))));
+        module.body.push(item);
+    }
+
+    lifted_names
+}
+
+fn extract_in_item(
+    ctx: &Ctx,
+    item: &mut ModuleItem,
+    names: &mut HashSet<Atom>,
+    lifted: &mut Vec<FnDecl>,
+    is_extracted: impl Fn(&str) -> bool,
+) {
+    for (name, mut component) in components(ctx, item) {
+        if !is_extracted(&name) {
+            continue;
+        }
+
+        let mut locals = BindingCollector::default();
+        component.visit_with(&mut locals);
+
+        let mut extractor = MapItemExtractor {
+            ctx,
+            component_name: &name,
+            locals: locals.names,
+            names,
+            lifted,
+        };
+        component.visit_mut_with(&mut extractor);
+    }
+}
+
+// The names declared at the top level of the module, which lifted components
+// must not clash with
+fn module_names(module: &Module) -> HashSet<Atom> {
+    let mut collector = BindingCollector::default();
+    module.visit_with(&mut collector);
+    collector.names
+}
+
+/// Collects the names of all bindings declared in a node
+#[derive(Default)]
+struct BindingCollector {
+    names: HashSet<Atom>,
+}
+
+impl Visit for BindingCollector {
+    fn visit_binding_ident(&mut self, binding: &BindingIdent) {
+        self.names.insert(binding.id.sym.clone());
+    }
+
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        self.names.insert(fn_decl.ident.sym.clone());
+        fn_decl.visit_children_with(self);
+    }
+
+    fn visit_class_decl(&mut self, class_decl: &ClassDecl) {
+        self.names.insert(class_decl.ident.sym.clone());
+        class_decl.visit_children_with(self);
+    }
+
+    fn visit_import_specifier(&mut self, specifier: &ImportSpecifier) {
+        let local = match specifier {
+            ImportSpecifier::Named(named) => &named.local,
+            ImportSpecifier::Default(default) => &default.local,
+            ImportSpecifier::Namespace(namespace) => &namespace.local,
+        };
+        self.names.insert(local.sym.clone());
+    }
+}
+
+struct MapItemExtractor<'a> {
+    ctx: &'a Ctx,
+    component_name: &'a str,
+    // Variables declared in the component, passed as props when used
+    locals: HashSet<Atom>,
+    names: &'a mut HashSet<Atom>,
+    lifted: &'a mut Vec<FnDecl>,
+}
+
+impl MapItemExtractor<'_> {
+    // Returns a name based on the component, e.g. `TodosItem`. It starts with
+    // an uppercase letter even for `default`, as React would otherwise render
+    // a DOM element.
+    fn item_name(&mut self) -> Atom {
+        let mut chars = self.component_name.chars();
+        let base = match chars.next() {
+            Some(first) => format!("{}{}Item", first.to_uppercase(), chars.as_str()),
+            None => "Item".to_string(),
+        };
+        let name: Atom = (1..)
+            .map(|index| match index {
+                1 => base.clone(),
+                index => format!("{}{}", base, index),
+            })
+            .map(Atom::from)
+            .find(|name| !self.names.contains(name) && !self.locals.contains(name))
+            .unwrap();
+
+        self.names.insert(name.clone());
+        name
+    }
+
+    // Lifts the callback into a component, returning the callback rendering it
+    // instead. Returns `None` when the callback can't be lifted safely.
+    fn lift(&mut self, callback: &ArrowExpr) -> Option<ArrowExpr> {
+        if callback.is_async || callback.is_generator || callback.params.is_empty() || callback.params.len() > 2 {
+            return None;
+        }
+
+        let params = callback
+            .params
+            .iter()
+            .map(|param| match param {
+                Pat::Ident(binding) if !RESERVED_PROPS.contains(&&*binding.id.sym) => Some(binding.id.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let BlockStmtOrExpr::Expr(body) = &*callback.body else {
+            return None;
+        };
+        let mut body = (**body).clone();
+        while let Expr::Paren(paren) = body {
+            body = *paren.expr;
+        }
+
+        // The key must stay on the element the callback returns
+        let key = match &mut body {
+            Expr::JSXElement(element) => take_key(element),
+            Expr::JSXFragment(_) => None,
+            _ => return None,
+        };
+
+        let mut free_vars = FreeVars {
+            scopes: vec![params.iter().map(|param| param.sym.clone()).collect()],
+            found: Vec::new(),
+            bail: false,
+        };
+        body.visit_with(&mut free_vars);
+        if free_vars.bail {
+            return None;
+        }
+
+        let captures: Vec<Ident> = free_vars
+            .found
+            .into_iter()
+            .filter(|ident| self.locals.contains(&ident.sym))
+            .collect();
+        if captures.iter().any(|ident| RESERVED_PROPS.contains(&&*ident.sym)) {
+            return None;
+        }
+
+        let props: Vec<Ident> = params.into_iter().chain(captures).collect();
+        let name = self.item_name();
+
+        self.lifted.push(item_component(&name, &props, body));
+
+        Some(ArrowExpr {
+            body: Box::new(BlockStmtOrExpr::Expr(Box::new(item_element(&name, key, &props)))),
+            ..callback.clone()
+        })
+    }
+}
+
+impl VisitMut for MapItemExtractor<'_> {
+    fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
+        call_expr.visit_mut_children_with(self);
+
+        if !is_map_call(self.ctx, call_expr) {
+            return;
+        }
+
+        let Expr::Arrow(callback) = &mut *call_expr.args[0].expr else {
+            return;
+        };
+
+        if let Some(lifted) = self.lift(callback) {
+            *callback = lifted;
+        }
+    }
+}
+
+// Checks for `items.map(item => <div />)`
+fn is_map_call(ctx: &Ctx, call_expr: &CallExpr) -> bool {
+    let Callee::Expr(callee) = &call_expr.callee else {
+        return false;
+    };
+    let Expr::Member(member) = &**callee else {
+        return false;
+    };
+
+    member.prop.as_ident().is_some_and(|prop| prop.sym == "map")
+        && call_expr.args.len() == 1
+        && call_expr.args[0].spread.is_none()
+        && contains_jsx_in_expr(ctx, &call_expr.args[0].expr)
+}
+
+fn take_key(element: &mut JSXElement) -> Option<JSXAttrValue> {
+    let index = element.opening.attrs.iter().position(|attr| {
+        matches!(attr, JSXAttrOrSpread::JSXAttr(JSXAttr { name: JSXAttrName::Ident(name), .. }) if name.sym == "key")
+    })?;
+
+    match element.opening.attrs.remove(index) {
+        JSXAttrOrSpread::JSXAttr(attr) => attr.value,
+        JSXAttrOrSpread::SpreadElement(_) => None,
+    }
+}
+
+// Creates `function TodosItem({ todo, prefix }) { return body; }`
+fn item_component(name: &Atom, props: &[Ident], body: Expr) -> FnDecl {
+    let param = Pat::Object(ObjectPat {
+        span: Default::default(),
+        props: props
+            .iter()
+            .map(|prop| {
+                ObjectPatProp::Assign(AssignPatProp {
+                    span: Default::default(),
+                    key: prop.clone().into(),
+                    value: None,
+                })
+            })
+            .collect(),
+        optional: false,
+        type_ann: None,
+    });
+
+    FnDecl {
+        ident: Ident::new_no_ctxt(name.clone(), Default::default()),
+        declare: false,
+        function: Box::new(Function {
+            params: vec![Param {
+                span: Default::default(),
+                decorators: Vec::new(),
+                pat: param,
+            }],
+            body: Some(BlockStmt {
+                stmts: vec![Stmt::Return(ReturnStmt {
+                    span: Default::default(),
+                    arg: Some(Box::new(body)),
+                })],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+    }
+}
+
+// Creates `<TodosItem key={todo.id} todo={todo} prefix={prefix} />`
+fn item_element(name: &Atom, key: Option<JSXAttrValue>, props: &[Ident]) -> Expr {
+    let key = key.map(|value| JSXAttr {
+        span: Default::default(),
+        name: JSXAttrName::Ident(IdentName::new("key".into(), Default::default())),
+        value: Some(value),
+    });
+
+    let props = props.iter().map(|prop| JSXAttr {
+        span: Default::default(),
+        name: JSXAttrName::Ident(IdentName::new(prop.sym.clone(), Default::default())),
+        value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            span: Default::default(),
+            expr: JSXExpr::Expr(Box::new(Expr::Ident(prop.clone()))),
+        })),
+    });
+
+    Expr::JSXElement(Box::new(JSXElement {
+        span: Default::default(),
+        opening: JSXOpeningElement {
+            span: Default::default(),
+            name: JSXElementName::Ident(Ident::new_no_ctxt(name.clone(), Default::default())),
+            attrs: key.into_iter().chain(props).map(JSXAttrOrSpread::JSXAttr).collect(),
+            self_closing: true,
+            type_args: None,
+        },
+        children: Vec::new(),
+        closing: None,
+    }))
+}
+
+// Collects the variables an expression uses that it doesn't declare, giving up
+// on anything that can't be moved into a component of its own
+struct FreeVars {
+    scopes: Vec<HashSet<Atom>>,
+    found: Vec<Ident>,
+    bail: bool,
+}
+
+impl Visit for FreeVars {
+    fn visit_ident(&mut self, ident: &Ident) {
+        if ident.sym == "arguments" {
+            self.bail = true;
+            return;
+        }
+
+        let is_bound = self.scopes.iter().any(|scope| scope.contains(&ident.sym));
+        if !is_bound && !self.found.iter().any(|found| found.sym == ident.sym) {
+            self.found.push(ident.clone());
+        }
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        let params = arrow
+            .params
+            .iter()
+            .map(|param| match param {
+                Pat::Ident(binding) => Some(binding.id.sym.clone()),
+                _ => None,
+            })
+            .collect::<Option<HashSet<_>>>();
+
+        match (params, &*arrow.body) {
+            (Some(params), BlockStmtOrExpr::Expr(body)) => {
+                self.scopes.push(params);
+                body.visit_with(self);
+                self.scopes.pop();
+            }
+            _ => self.bail = true,
+        }
+    }
+
+    fn visit_function(&mut self, _: &Function) {
+        self.bail = true;
+    }
+
+    fn visit_class(&mut self, _: &Class) {
+        self.bail = true;
+    }
+
+    fn visit_this_expr(&mut self, _: &ThisExpr) {
+        self.bail = true;
+    }
+
+    fn visit_await_expr(&mut self, _: &AwaitExpr) {
+        self.bail = true;
+    }
+
+    fn visit_yield_expr(&mut self, _: &YieldExpr) {
+        self.bail = true;
+    }
+
+    fn visit_assign_expr(&mut self, _: &AssignExpr) {
+        self.bail = true;
+    }
+
+    fn visit_update_expr(&mut self, _: &UpdateExpr) {
+        self.bail = true;
+    }
+}
@@ -0,0 +1,398 @@
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::{Config, ConfigError};
+
+// Extensions of files that can contain components. Anything else handed to
+// the plugin (e.g. compiled `.mdx` or `.json` modules) is left alone.
+const SOURCE_EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts"];
+
+// Declaration files never contain runtime code
+const DECLARATION_SUFFIXES: &[&str] = &[".d.ts", ".d.mts", ".d.cts"];
+
+/// Decides which files the plugin transforms, combining the built-in
+/// extension filter with the `include` and `exclude` patterns of a [Config].
+///
+/// A file is transformed when it has a source extension, matches at least one
+/// `include` pattern (or no `include` patterns are configured) and matches no
+/// `exclude` pattern.
+///
+/// Files inside a `node_modules` directory are only transformed when their
+/// package name matches one of the `include_node_modules` globs, e.g. `@app/*`.
+/// Such files are selected by package name instead of `include`, but `exclude`
+/// still applies to them.
+///
+/// Patterns follow `.gitignore` rules and are resolved relative to the cwd:
+///
+/// - a pattern containing a `/` (other than a trailing one) is anchored to the
+///   cwd, otherwise it matches at any depth
+/// - `*` never crosses a `/`, `**` matches any number of directories
+/// - a pattern matching a directory matches everything inside it, and a
+///   trailing `/` only matches directories
+/// - a leading `!` negates the pattern, and the last matching pattern wins,
+///   but a file inside an excluded directory can't be re-included
+///
+/// Setting `legacy_exclude_matching` restores the previous heuristics, which
+/// match patterns against any suffix or subsequence of the path.
+///
+/// Invalid globs are reported as a [ConfigError] naming the offending key.
+#[derive(Debug, Clone)]
+pub struct FileMatcher {
+    include: Option<PathPatterns>,
+    exclude: Option<PathPatterns>,
+    node_modules: Option<GlobSet>,
+}
+
+impl FileMatcher {
+    pub fn new(config: &Config) -> Result<Self, ConfigError> {
+        Ok(FileMatcher {
+            include: PathPatterns::new("include", &config.include, config)?,
+            exclude: PathPatterns::new("exclude", &config.exclude, config)?,
+            node_modules: package_globs("include_node_modules", &config.include_node_modules)?,
+        })
+    }
+
+    /// Returns whether the file should be transformed. `cwd` is the directory
+    /// patterns are resolved against, when known.
+    pub fn is_match(&self, file_path: &str, cwd: Option<&str>) -> bool {
+        if !has_source_extension(file_path) {
+            return false;
+        }
+
+        match node_modules_package(file_path) {
+            Some(package) => {
+                let is_included = self
+                    .node_modules
+                    .as_ref()
+                    .is_some_and(|globs| globs.is_match(package));
+
+                if !is_included {
+                    return false;
+                }
+            }
+            None => {
+                if let Some(include) = &self.include {
+                    if !include.is_match(file_path, cwd) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        match &self.exclude {
+            Some(exclude) => !exclude.is_match(file_path, cwd),
+            None => true,
+        }
+    }
+}
+
+// Files without an extension (e.g. code passed on stdin) are still processed,
+// as there is nothing to tell us they aren't JavaScript.
+fn has_source_extension(file_path: &str) -> bool {
+    if DECLARATION_SUFFIXES.iter().any(|suffix| file_path.ends_with(suffix)) {
+        return false;
+    }
+
+    match Path::new(file_path).extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => SOURCE_EXTENSIONS.contains(&ext),
+        None => true,
+    }
+}
+
+// Returns the name of the package a file inside `node_modules` belongs to.
+// Only whole path segments count, and for nested dependencies (including the
+// `node_modules/.pnpm` store) the innermost package is used.
+fn node_modules_package(file_path: &str) -> Option<String> {
+    let segments: Vec<&str> = file_path.split(['/', '\\']).collect();
+    let index = segments.iter().rposition(|segment| *segment == "node_modules")?;
+
+    let package = match &segments[index + 1..] {
+        [scope, name, ..] if scope.starts_with('@') => format!("{}/{}", scope, name),
+        [name, ..] => name.to_string(),
+        [] => String::new(),
+    };
+
+    Some(package)
+}
+
+fn package_globs(key: &str, patterns: &[String]) -> Result<Option<GlobSet>, ConfigError> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+
+    for (index, pattern) in patterns.iter().enumerate() {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|err| invalid_glob(key, index, pattern, err))?;
+        builder.add(glob);
+    }
+
+    build_globset(key, builder).map(Some)
+}
+
+/// Compiles globs matching names rather than paths, e.g. `*Provider`
+pub(crate) fn name_globs(key: &str, patterns: &[String]) -> Result<GlobSet, ConfigError> {
+    let mut builder = GlobSetBuilder::new();
+
+    for (index, pattern) in patterns.iter().enumerate() {
+        let glob = Glob::new(pattern).map_err(|err| invalid_glob(key, index, pattern, err))?;
+        builder.add(glob);
+    }
+
+    build_globset(key, builder)
+}
+
+fn invalid_glob(key: &str, index: usize, pattern: &str, err: globset::Error) -> ConfigError {
+    ConfigError::InvalidValue {
+        key: format!("{}[{}]", key, index),
+        value: pattern.to_string(),
+        reason: err.kind().to_string(),
+    }
+}
+
+fn build_globset(key: &str, builder: GlobSetBuilder) -> Result<GlobSet, ConfigError> {
+    builder
+        .build()
+        .map_err(|err| ConfigError::Invalid(format!("`{}`: {}", key, err)))
+}
+
+#[derive(Debug, Clone)]
+enum PathPatterns {
+    Gitignore(GitignoreSet),
+    Legacy(PatternSet),
+}
+
+impl PathPatterns {
+    fn new(key: &str, patterns: &[String], config: &Config) -> Result<Option<Self>, ConfigError> {
+        if config.legacy_exclude_matching {
+            Ok(PatternSet::new(key, patterns)?.map(PathPatterns::Legacy))
+        } else {
+            Ok(GitignoreSet::new(key, patterns, config.case_insensitive)?.map(PathPatterns::Gitignore))
+        }
+    }
+
+    fn is_match(&self, file_path: &str, cwd: Option<&str>) -> bool {
+        match self {
+            PathPatterns::Gitignore(set) => set.is_match(&relative_path(file_path, cwd)),
+            PathPatterns::Legacy(set) => set.is_match(file_path),
+        }
+    }
+}
+
+// Makes the path relative to the cwd, using `/` as separator. Paths outside of
+// the cwd are kept absolute, so anchored patterns never match them.
+fn relative_path<'a>(file_path: &'a str, cwd: Option<&str>) -> Cow<'a, str> {
+    let file_path: Cow<str> = if file_path.contains('\\') {
+        Cow::Owned(file_path.replace('\\', "/"))
+    } else {
+        Cow::Borrowed(file_path)
+    };
+
+    let cwd = match cwd {
+        Some(cwd) if !cwd.is_empty() => cwd.replace('\\', "/"),
+        _ => return file_path,
+    };
+
+    match file_path
+        .strip_prefix(cwd.trim_end_matches('/'))
+        .and_then(|rest| rest.strip_prefix('/'))
+    {
+        Some(relative) => Cow::Owned(relative.to_string()),
+        None => file_path,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GitignoreSet {
+    globset: GlobSet,
+    // For every glob in the set, the index of the pattern it came from,
+    // whether that pattern is negated and whether it only matches directories
+    globs: Vec<(usize, bool, bool)>,
+}
+
+impl GitignoreSet {
+    // Returns `None` when there are no patterns
+    fn new(key: &str, patterns: &[String], case_insensitive: bool) -> Result<Option<Self>, ConfigError> {
+        let mut builder = GlobSetBuilder::new();
+        let mut globs = Vec::new();
+
+        for (index, raw_pattern) in patterns.iter().enumerate() {
+            let (negated, pattern) = match raw_pattern.strip_prefix('!') {
+                Some(pattern) => (true, pattern),
+                None => (false, raw_pattern.as_str()),
+            };
+
+            let Some((glob, is_directory)) = gitignore_glob(pattern) else {
+                continue;
+            };
+            let glob = GlobBuilder::new(&glob)
+                .literal_separator(true)
+                .case_insensitive(case_insensitive)
+                .build()
+                .map_err(|err| invalid_glob(key, index, raw_pattern, err))?;
+            builder.add(glob);
+            globs.push((index, negated, is_directory));
+        }
+
+        if globs.is_empty() {
+            return Ok(None);
+        }
+
+        let globset = build_globset(key, builder)?;
+
+        Ok(Some(GitignoreSet { globset, globs }))
+    }
+
+    fn is_match(&self, relative_path: &str) -> bool {
+        // Like git, a matching directory matches everything inside it, and
+        // nothing inside it can be re-included
+        let directories = relative_path
+            .match_indices('/')
+            .map(|(end, _)| &relative_path[..end])
+            .filter(|directory| !directory.is_empty());
+        for directory in directories {
+            if self.matches(directory, true) == Some(true) {
+                return true;
+            }
+        }
+
+        self.matches(relative_path, false).unwrap_or(false)
+    }
+
+    // The last matching pattern decides, like in a .gitignore file. Returns
+    // `None` when no pattern matches.
+    fn matches(&self, path: &str, is_directory: bool) -> Option<bool> {
+        self.globset
+            .matches(path)
+            .into_iter()
+            .map(|glob_index| self.globs[glob_index])
+            .filter(|(_, _, directory_only)| is_directory || !directory_only)
+            .max_by_key(|(index, _, _)| *index)
+            .map(|(_, negated, _)| !negated)
+    }
+}
+
+// Translates a single .gitignore style pattern into the glob matching it, and
+// whether it only matches directories
+fn gitignore_glob(pattern: &str) -> Option<(String, bool)> {
+    let pattern = pattern.trim();
+    let (is_directory, pattern) = match pattern.strip_suffix('/') {
+        Some(pattern) => (true, pattern),
+        None => (false, pattern),
+    };
+
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let anchored = match pattern.strip_prefix('/') {
+        Some(pattern) => pattern.to_string(),
+        None if pattern.contains('/') => pattern.to_string(),
+        None => format!("**/{}", pattern),
+    };
+
+    Some((anchored, is_directory))
+}
+
+#[derive(Debug, Clone)]
+struct PatternSet {
+    patterns: Vec<String>,
+    globset: GlobSet,
+}
+
+impl PatternSet {
+    // Returns `None` when there are no patterns
+    fn new(key: &str, patterns: &[String]) -> Result<Option<Self>, ConfigError> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+
+        let mut builder = GlobSetBuilder::new();
+
+        for (index, pattern) in patterns.iter().enumerate() {
+            // Create a glob for each pattern and add to the set
+            let glob = Glob::new(pattern).map_err(|err| invalid_glob(key, index, pattern, err))?;
+            builder.add(glob);
+        }
+
+        Ok(Some(PatternSet {
+            patterns: patterns.to_vec(),
+            globset: build_globset(key, builder)?,
+        }))
+    }
+
+    fn is_match(&self, file_path: &str) -> bool {
+        let path = Path::new(file_path);
+
+        // Get just the file name for simpler matching
+        let file_name = path.file_name().and_then(|f| f.to_str()).unwrap_or("");
+
+        // Get the components of the path for more precise matching
+        let path_components: Vec<&str> = file_path.split(std::path::MAIN_SEPARATOR).collect();
+
+        // Try to match against the full absolute path
+        if self.globset.is_match(file_path) {
+            return true;
+        }
+
+        // Try to match against just the file name
+        if self.globset.is_match(file_name) {
+            return true;
+        }
+
+        // Check if any pattern matches the path components
+        // This helps with relative path patterns like "src/Test.tsx"
+        for pattern in &self.patterns {
+            let pattern_components: Vec<&str> = pattern.split('/').collect();
+
+            // Try to find the pattern components as a subsequence in the path
+            if path_components_match(&path_components, &pattern_components) {
+                return true;
+            }
+        }
+
+        // Also try to match against path relative from project root
+        // This handles cases where exclude pattern is like "src/Test.tsx"
+        let separator = std::path::MAIN_SEPARATOR.to_string();
+        (0..path_components.len()).any(|i| {
+            let potential_relative_path = path_components[i..].join(&separator);
+            self.globset.is_match(potential_relative_path)
+        })
+    }
+}
+
+// Helper function to check if a path should be excluded, using the legacy
+// heuristics
+pub fn should_exclude(file_path: &str, exclude_patterns: &[String]) -> bool {
+    match PatternSet::new("exclude", exclude_patterns) {
+        Ok(Some(exclude)) => exclude.is_match(file_path),
+        _ => false,
+    }
+}
+
+// Helper function to check if pattern components appear as a subsequence in path components
+pub(crate) fn path_components_match(path_components: &[&str], pattern_components: &[&str]) -> bool {
+    if pattern_components.is_empty() {
+        return true;
+    }
+
+    // Look for the pattern components in sequence within the path components
+    let mut path_idx = 0;
+    let mut pattern_idx = 0;
+
+    while path_idx < path_components.len() && pattern_idx < pattern_components.len() {
+        if path_components[path_idx].to_lowercase() == pattern_components[pattern_idx].to_lowercase() {
+            pattern_idx += 1;
+            if pattern_idx == pattern_components.len() {
+                return true;
+            }
+        }
+        path_idx += 1;
+    }
+
+    false
+}
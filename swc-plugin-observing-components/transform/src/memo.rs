@@ -0,0 +1,54 @@
+//! `memo` calls replaced by the observer
+
+use swc_atoms::Atom;
+use swc_ecma_ast::*;
+
+use crate::jsx;
+
+/// The names `memo` of `react` is bound to in a module, so components like
+/// `memo(() => <tr />)` can be made `observer(() => <tr />)` instead of being
+/// memoized twice.
+#[derive(Debug, Default)]
+pub(crate) struct ReactMemo {
+    /// Local names of `memo`, e.g. `memo` for `import { memo } from "react"`
+    functions: Vec<Atom>,
+    /// Local names of `react` itself, for `React.memo`
+    namespaces: Vec<Atom>,
+}
+
+impl ReactMemo {
+    pub(crate) fn new(module: &Module) -> Self {
+        let mut memo = ReactMemo::default();
+
+        for (source, imported, local) in jsx::imports(module) {
+            match imported {
+                _ if source != "react" => {}
+                "memo" => memo.functions.push(local.sym.clone()),
+                "default" | "*" => memo.namespaces.push(local.sym.clone()),
+                _ => {}
+            }
+        }
+
+        memo
+    }
+
+    /// Checks for `memo(fn)` or `React.memo(fn)`, with or without comparator
+    pub(crate) fn is_memo_call(&self, call_expr: &CallExpr) -> bool {
+        let Callee::Expr(callee) = &call_expr.callee else {
+            return false;
+        };
+
+        let is_memo = match &**callee {
+            Expr::Ident(ident) => self.functions.contains(&ident.sym),
+            Expr::Member(member) => {
+                member.prop.as_ident().is_some_and(|prop| prop.sym == "memo")
+                    && matches!(&*member.obj, Expr::Ident(obj) if self.namespaces.contains(&obj.sym))
+            }
+            _ => false,
+        };
+
+        is_memo
+            && matches!(call_expr.args.len(), 1 | 2)
+            && call_expr.args.iter().all(|arg| arg.spread.is_none())
+    }
+}
@@ -0,0 +1,64 @@
+//! Component names, and the names of functions creating lazy components
+
+use globset::GlobSet;
+
+use swc_ecma_ast::*;
+
+use crate::{context::Ctx, matcher, ComponentNames, Config};
+
+/// The `component_names`, `skip_components` and `lazy_factories` of the
+/// configuration
+#[derive(Debug, Default)]
+pub(crate) struct Names {
+    component_names: ComponentNames,
+    skip_components: Option<GlobSet>,
+    lazy_factories: Vec<String>,
+}
+
+impl Names {
+    pub(crate) fn new(config: &Config) -> Self {
+        Names {
+            component_names: config.component_names.clone(),
+            // Validated along with the rest of the configuration
+            skip_components: matcher::name_globs("skip_components", &config.skip_components).ok(),
+            lazy_factories: config.lazy_factories().into_iter().map(|factory| factory.to_string()).collect(),
+        }
+    }
+}
+
+/// Checks whether a function or variable is named like a component, see
+/// [ComponentNames]. Skipped components aren't.
+pub(crate) fn is_component_name(ctx: &Ctx, name: &str) -> bool {
+    ctx.names.component_names.is_match(name) && !is_skipped(ctx, name)
+}
+
+/// Checks whether a component is listed in `skip_components`
+pub(crate) fn is_skipped(ctx: &Ctx, name: &str) -> bool {
+    ctx.names
+        .skip_components
+        .as_ref()
+        .is_some_and(|skip_components| skip_components.is_match(name))
+}
+
+/// Checks for lazily loaded components, e.g. `lazy(() => import("./Settings"))`
+/// or `React.lazy(...)`, which must not be wrapped
+pub(crate) fn is_lazy_call(ctx: &Ctx, expr: &Expr) -> bool {
+    let Expr::Call(CallExpr {
+        callee: Callee::Expr(callee),
+        ..
+    }) = expr
+    else {
+        return false;
+    };
+
+    let name = match &**callee {
+        Expr::Ident(ident) => &ident.sym,
+        Expr::Member(member) => match member.prop.as_ident() {
+            Some(prop) => &prop.sym,
+            None => return false,
+        },
+        _ => return false,
+    };
+
+    ctx.names.lazy_factories.iter().any(|factory| *factory == **name)
+}
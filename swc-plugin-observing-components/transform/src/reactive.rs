@@ -0,0 +1,128 @@
+//! Wrapping only the components that can read observables
+
+use std::fmt;
+
+use swc_atoms::Atom;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{components::Component, context::Ctx, contains_jsx_in_expr};
+
+/// The bindings a module imports from the `reactive_sources`, e.g. the stores
+/// and hooks of `./stores`. Components using none of them and taking no props
+/// can't read observables, so they are left alone.
+#[derive(Debug)]
+pub(crate) struct ReactiveSources {
+    locals: Vec<Atom>,
+}
+
+/// Why a component is made an observer
+#[derive(Debug)]
+pub(crate) enum Reason {
+    /// It uses a binding imported from a reactive source
+    Uses(Atom),
+    /// Its props may be observables
+    Props,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::Uses(name) => write!(f, "it uses `{}` from a reactive source", name),
+            Reason::Props => write!(f, "it receives props"),
+        }
+    }
+}
+
+impl ReactiveSources {
+    pub(crate) fn new(module: &Module, sources: &[String]) -> Self {
+        let locals = module
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+                    if sources.iter().any(|source| *source == *import.src.value) =>
+                {
+                    Some(import)
+                }
+                _ => None,
+            })
+            .flat_map(|import| import.specifiers.iter())
+            .map(|specifier| match specifier {
+                ImportSpecifier::Named(named) => named.local.sym.clone(),
+                ImportSpecifier::Default(default) => default.local.sym.clone(),
+                ImportSpecifier::Namespace(namespace) => namespace.local.sym.clone(),
+            })
+            .collect();
+
+        ReactiveSources { locals }
+    }
+
+    /// Returns why a component should be an observer, or `None` for static
+    /// components
+    pub(crate) fn reason(&self, ctx: &Ctx, component: &Component) -> Option<Reason> {
+        match component {
+            Component::Function(function) => self.reason_in_function(function),
+            Component::Expr(expr) => self.reason_in_expr(ctx, expr),
+        }
+    }
+
+    /// Returns why the component function should be an observer, or `None`
+    /// for static components
+    pub(crate) fn reason_in_function(&self, function: &Function) -> Option<Reason> {
+        if function.params.first().is_some_and(|param| has_props(&param.pat)) {
+            return Some(Reason::Props);
+        }
+
+        self.find_use(|finder| function.body.visit_with(finder))
+    }
+
+    /// Like [ReactiveSources::reason_in_function], for function expressions
+    /// and functions passed to HOCs, e.g. `forwardRef((props, ref) => ...)`
+    pub(crate) fn reason_in_expr(&self, ctx: &Ctx, expr: &Expr) -> Option<Reason> {
+        match expr {
+            Expr::Paren(paren) => self.reason_in_expr(ctx, &paren.expr),
+            Expr::Fn(fn_expr) => self.reason_in_function(&fn_expr.function),
+            Expr::Arrow(arrow) => {
+                if arrow.params.first().is_some_and(has_props) {
+                    return Some(Reason::Props);
+                }
+
+                self.find_use(|finder| arrow.body.visit_with(finder))
+            }
+            Expr::Call(call_expr) => call_expr
+                .args
+                .iter()
+                .filter(|arg| contains_jsx_in_expr(ctx, &arg.expr))
+                .find_map(|arg| self.reason_in_expr(ctx, &arg.expr)),
+            _ => None,
+        }
+    }
+
+    fn find_use(&self, visit: impl FnOnce(&mut UseFinder)) -> Option<Reason> {
+        let mut finder = UseFinder {
+            locals: &self.locals,
+            found: None,
+        };
+        visit(&mut finder);
+        finder.found.map(Reason::Uses)
+    }
+}
+
+// Checks whether a component takes props, which `()` and `({})` don't
+fn has_props(pat: &Pat) -> bool {
+    !matches!(pat, Pat::Object(object) if object.props.is_empty())
+}
+
+struct UseFinder<'a> {
+    locals: &'a [Atom],
+    found: Option<Atom>,
+}
+
+impl Visit for UseFinder<'_> {
+    fn visit_ident(&mut self, ident: &Ident) {
+        if self.found.is_none() && self.locals.contains(&ident.sym) {
+            self.found = Some(ident.sym.clone());
+        }
+    }
+}
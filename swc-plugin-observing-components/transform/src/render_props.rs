@@ -0,0 +1,172 @@
+//! Render props rendered through `<Observer>`
+
+use swc_ecma_ast::*;
+use swc_ecma_visit::{VisitMut, VisitMutWith};
+
+use crate::{context::Ctx, contains_jsx_in_expr, RenderProps};
+
+/// Rewrites render props like `renderItem={item => <Row item={item} />}` into
+/// `renderItem={item => <Observer>{() => <Row item={item} />}</Observer>}`, so
+/// what they render is tracked even though other components call them.
+pub(crate) struct RenderPropsObserver<'a> {
+    ctx: &'a Ctx,
+    render_props: &'a RenderProps,
+    observer_name: String,
+    /// Whether a render prop was rewritten, so `<Observer>` must be imported
+    pub(crate) has_observer: bool,
+}
+
+impl<'a> RenderPropsObserver<'a> {
+    pub(crate) fn new(ctx: &'a Ctx, render_props: &'a RenderProps) -> Self {
+        RenderPropsObserver {
+            ctx,
+            render_props,
+            observer_name: render_props.observer_name().to_string(),
+            has_observer: false,
+        }
+    }
+
+    fn is_observer_element(&self, element: &JSXElement) -> bool {
+        match &element.opening.name {
+            JSXElementName::Ident(ident) => ident.sym == *self.observer_name,
+            _ => false,
+        }
+    }
+
+    fn is_render_prop(&self, attr: &JSXAttr) -> bool {
+        match &attr.name {
+            JSXAttrName::Ident(ident) => self.render_props.attributes.iter().any(|name| ident.sym == **name),
+            JSXAttrName::JSXNamespacedName(_) => false,
+        }
+    }
+
+    // Makes the render function render its result through `<Observer>`
+    fn observe_render_fn(&mut self, expr: &mut Expr) {
+        if !contains_jsx_in_expr(self.ctx, expr) {
+            return;
+        }
+
+        match expr {
+            Expr::Arrow(arrow) => {
+                if self.is_observed_body(&arrow.body) {
+                    return;
+                }
+
+                let body = std::mem::replace(&mut *arrow.body, BlockStmtOrExpr::BlockStmt(Default::default()));
+                *arrow.body = BlockStmtOrExpr::Expr(Box::new(self.observer_element(body)));
+            }
+            Expr::Fn(fn_expr) => {
+                let Some(body) = fn_expr.function.body.take() else {
+                    return;
+                };
+
+                let element = self.observer_element(BlockStmtOrExpr::BlockStmt(body));
+                fn_expr.function.body = Some(BlockStmt {
+                    stmts: vec![Stmt::Return(ReturnStmt {
+                        span: Default::default(),
+                        arg: Some(Box::new(element)),
+                    })],
+                    ..Default::default()
+                });
+            }
+            Expr::Paren(paren) => self.observe_render_fn(&mut paren.expr),
+            _ => {}
+        }
+    }
+
+    // Checks for render functions already returning `<Observer>`
+    fn is_observed_body(&self, body: &BlockStmtOrExpr) -> bool {
+        let BlockStmtOrExpr::Expr(expr) = body else {
+            return false;
+        };
+
+        let mut expr: &Expr = expr;
+        while let Expr::Paren(paren) = expr {
+            expr = &paren.expr;
+        }
+
+        matches!(expr, Expr::JSXElement(element) if self.is_observer_element(element))
+    }
+
+    // Creates `<Observer>{() => body}</Observer>`
+    fn observer_element(&mut self, body: BlockStmtOrExpr) -> Expr {
+        self.has_observer = true;
+
+        let name = JSXElementName::Ident(Ident::new(
+            self.observer_name.as_str().into(),
+            Default::default(),
+            Default::default(),
+        ));
+
+        let render = Expr::Arrow(ArrowExpr {
+            span: Default::default(),
+            ctxt: Default::default(),
+            params: Vec::new(),
+            body: Box::new(body),
+            is_async: false,
+            is_generator: false,
+            type_params: None,
+            return_type: None,
+        });
+
+        Expr::JSXElement(Box::new(JSXElement {
+            span: Default::default(),
+            opening: JSXOpeningElement {
+                span: Default::default(),
+                name: name.clone(),
+                attrs: Vec::new(),
+                self_closing: false,
+                type_args: None,
+            },
+            children: vec![JSXElementChild::JSXExprContainer(JSXExprContainer {
+                span: Default::default(),
+                expr: JSXExpr::Expr(Box::new(render)),
+            })],
+            closing: Some(JSXClosingElement {
+                span: Default::default(),
+                name,
+            }),
+        }))
+    }
+}
+
+impl VisitMut for RenderPropsObserver<'_> {
+    fn visit_mut_jsx_element(&mut self, element: &mut JSXElement) {
+        element.visit_mut_children_with(self);
+
+        // The children of `<Observer>` are its own render function
+        if self.is_observer_element(element) {
+            return;
+        }
+
+        for attr in element.opening.attrs.iter_mut() {
+            let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+                continue;
+            };
+
+            if !self.is_render_prop(attr) {
+                continue;
+            }
+
+            if let Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(expr),
+                ..
+            })) = &mut attr.value
+            {
+                self.observe_render_fn(expr);
+            }
+        }
+
+        if self.render_props.children {
+            for child in element.children.iter_mut() {
+                if let JSXElementChild::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                }) = child
+                {
+                    self.observe_render_fn(expr);
+                }
+            }
+        }
+    }
+}
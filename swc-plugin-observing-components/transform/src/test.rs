@@ -1,6 +1,77 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    use swc_common::{
+        comments::SingleThreadedComments,
+        errors::{Handler, HANDLER},
+        sync::Lrc,
+        FileName, SourceMap,
+    };
+    use swc_ecma_ast::{EsVersion, Module, Pass, Program};
+    use swc_ecma_parser::{parse_file_as_module, EsSyntax, Syntax};
+
+    use crate::matcher::{path_components_match, should_exclude};
+    use crate::{context::Ctx, jsx::JsxPragmas, map_items::extract_map_items};
+    use crate::{
+        observer_transform, resolve_import_path, CachedConfig, Config, ConfigError, FileMatcher, HocOrder, ImportStyle,
+        Preset, Strategy,
+    };
+
+    // Collects what the handler emits
+    #[derive(Clone, Default)]
+    struct Diagnostics(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Diagnostics {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // Runs the observer transform on the code, returning the warnings and errors
+    // it emits, which the fixtures don't capture
+    fn diagnostics(config: &str, code: &str) -> String {
+        let config = Config::from_json(config).unwrap();
+        let cm: Lrc<SourceMap> = Default::default();
+        let diagnostics = Diagnostics::default();
+        let handler = Handler::with_emitter_writer(Box::new(diagnostics.clone()), Some(cm.clone()));
+
+        let comments = SingleThreadedComments::default();
+        let module = parse(&cm, &comments, code);
+
+        let mut program = Program::Module(module);
+        HANDLER.set(&handler, || observer_transform(config, "input.js", comments).process(&mut program));
+
+        let output = diagnostics.0.lock().unwrap();
+        String::from_utf8_lossy(&output).into_owned()
+    }
+
+    fn parse(cm: &Lrc<SourceMap>, comments: &SingleThreadedComments, code: &str) -> Module {
+        let fm = cm.new_source_file(FileName::Anon.into(), code.to_string());
+        let syntax = Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        });
+        parse_file_as_module(&fm, syntax, EsVersion::latest(), Some(comments), &mut Vec::new()).unwrap()
+    }
+
+    // Returns the names of the `.map` items lifted out of the code
+    fn lifted_map_items(code: &str) -> Vec<String> {
+        let config = Config::from_json(r#"{ "import_path": "mobx-react-lite", "extract_map_items": true }"#).unwrap();
+        let cm: Lrc<SourceMap> = Default::default();
+        let comments = SingleThreadedComments::default();
+        let mut module = parse(&cm, &comments, code);
+
+        let ctx = Ctx::new(&module, &config, &JsxPragmas::of_module(&module, &comments));
+        extract_map_items(&ctx, &mut module, None).iter().map(|name| name.to_string()).collect()
+    }
     
     #[test]
     fn test_should_exclude() {
@@ -46,4 +117,507 @@ mod tests {
         let pattern: Vec<&str> = vec![];
         assert!(path_components_match(&path, &pattern), "Empty pattern should match");
     }
+
+    #[test]
+    fn test_file_matcher_include() {
+        let config = Config {
+            include: vec!["src/**/*.tsx".to_string()],
+            exclude: vec!["*.stories.tsx".to_string()],
+            ..Default::default()
+        };
+        let matcher = FileMatcher::new(&config).unwrap();
+        let cwd = Some("/project");
+
+        assert!(matcher.is_match("/project/src/components/Button.tsx", cwd), "Should include matching file");
+        assert!(!matcher.is_match("/project/src/components/Button.stories.tsx", cwd), "Exclude should win over include");
+        assert!(!matcher.is_match("/project/src/utils/format.ts", cwd), "Shouldn't include non-matching file");
+        assert!(!matcher.is_match("/project/lib/src/Button.tsx", cwd), "Include should be anchored to the cwd");
+    }
+
+    #[test]
+    fn test_file_matcher_extensions() {
+        let matcher = FileMatcher::new(&Config::default()).unwrap();
+
+        assert!(matcher.is_match("/project/src/App.jsx", None), "Should process source files");
+        assert!(matcher.is_match("/project/src/App.mts", None), "Should process module source files");
+        assert!(!matcher.is_match("/project/src/types.d.ts", None), "Shouldn't process declaration files");
+        assert!(!matcher.is_match("/project/docs/intro.mdx", None), "Shouldn't process non-source files");
+        assert!(matcher.is_match("", None), "Should process code without a file name");
+    }
+
+    #[test]
+    fn test_file_matcher_exclude_is_cwd_relative() {
+        let matcher = FileMatcher::new(&Config {
+            exclude: vec!["src/Test.tsx".to_string()],
+            ..Default::default()
+        }).unwrap();
+        let cwd = Some("/repo");
+
+        assert!(!matcher.is_match("/repo/src/Test.tsx", cwd), "Should exclude path relative to cwd");
+        assert!(matcher.is_match("/repo/src/legacy/deep/Test.tsx", cwd), "Shouldn't exclude nested file with same name");
+        assert!(matcher.is_match("/repo/packages/ui/src/Test.tsx", cwd), "Shouldn't exclude file in other package");
+        assert!(matcher.is_match("/elsewhere/src/Test.tsx", cwd), "Shouldn't exclude file outside of cwd");
+        assert!(matcher.is_match("/repo/src/test.tsx", cwd), "Should match case-sensitively by default");
+        assert!(!matcher.is_match("C:\\repo\\src\\Test.tsx", Some("C:\\repo")), "Should handle Windows paths");
+    }
+
+    #[test]
+    fn test_file_matcher_gitignore_patterns() {
+        let matcher = FileMatcher::new(&Config {
+            exclude: vec![
+                "generated/".to_string(),
+                "*.test.tsx".to_string(),
+                "src/ui/**".to_string(),
+                "!src/ui/Button.tsx".to_string(),
+            ],
+            ..Default::default()
+        }).unwrap();
+        let cwd = Some("/repo");
+
+        assert!(!matcher.is_match("/repo/src/generated/Api.tsx", cwd), "Directory pattern should match at any depth");
+        assert!(matcher.is_match("/repo/src/generated.tsx", cwd), "Directory pattern shouldn't match files");
+        assert!(!matcher.is_match("/repo/src/deep/App.test.tsx", cwd), "Unanchored pattern should match at any depth");
+        assert!(!matcher.is_match("/repo/src/ui/forms/Input.tsx", cwd), "Should match ** across directories");
+        assert!(matcher.is_match("/repo/src/ui/Button.tsx", cwd), "Negated pattern should re-include file");
+    }
+
+    #[test]
+    fn test_file_matcher_gitignore_excluded_directory() {
+        let matcher = FileMatcher::new(&Config {
+            exclude: vec![
+                "src/ui/".to_string(),
+                "!src/ui/Button.tsx".to_string(),
+                "legacy/**".to_string(),
+                "!legacy/forms/Input.tsx".to_string(),
+                "!legacy/App.tsx".to_string(),
+            ],
+            ..Default::default()
+        }).unwrap();
+        let cwd = Some("/repo");
+
+        assert!(!matcher.is_match("/repo/src/ui/Button.tsx", cwd), "Shouldn't re-include a file of an excluded directory");
+        assert!(!matcher.is_match("/repo/legacy/forms/Input.tsx", cwd), "Shouldn't re-include a file of an excluded subdirectory");
+        assert!(matcher.is_match("/repo/legacy/App.tsx", cwd), "Should re-include a file when only the contents are excluded");
+        assert!(matcher.is_match("/repo/src/App.tsx", cwd));
+    }
+
+    #[test]
+    fn test_file_matcher_case_insensitive() {
+        let matcher = FileMatcher::new(&Config {
+            exclude: vec!["src/test.tsx".to_string()],
+            case_insensitive: true,
+            ..Default::default()
+        }).unwrap();
+
+        assert!(!matcher.is_match("/repo/src/Test.tsx", Some("/repo")), "Should match case-insensitively");
+    }
+
+    #[test]
+    fn test_file_matcher_legacy() {
+        let matcher = FileMatcher::new(&Config {
+            exclude: vec!["src/Test.tsx".to_string()],
+            legacy_exclude_matching: true,
+            ..Default::default()
+        }).unwrap();
+
+        assert!(!matcher.is_match("/repo/src/legacy/deep/Test.tsx", Some("/repo")), "Legacy matching should match subsequences");
+    }
+
+    #[test]
+    fn test_cached_config() {
+        let raw = r#"{ "import_path": "mobx-react-lite", "exclude": ["src/legacy/"] }"#;
+        let first = CachedConfig::new(raw).unwrap();
+        let second = CachedConfig::new(raw).unwrap();
+
+        assert!(std::ptr::eq(&*first, &*second), "Should reuse the compiled configuration");
+        assert!(!second.matcher.is_match("/repo/src/legacy/App.tsx", Some("/repo")), "Should compile the exclude patterns");
+        assert!(CachedConfig::new("{ \"exclude\": [] }").is_err(), "Should reject invalid configuration");
+
+        for index in 0..8 {
+            CachedConfig::new(&format!(r#"{{ "import_path": "./observer-{}" }}"#, index)).unwrap();
+        }
+        let third = CachedConfig::new(raw).unwrap();
+        assert!(!std::ptr::eq(&*first, &*third), "Should evict the least recently used configuration");
+    }
+
+    #[test]
+    fn test_file_matcher_node_modules() {
+        let matcher = FileMatcher::new(&Config {
+            include: vec!["src/**".to_string()],
+            exclude: vec!["**/*.stories.tsx".to_string()],
+            include_node_modules: vec!["@app/*".to_string(), "design-system".to_string()],
+            ..Default::default()
+        }).unwrap();
+        let cwd = Some("/repo/apps/web");
+
+        assert!(!matcher.is_match("/repo/apps/web/node_modules/react/index.js", cwd), "Should skip dependencies");
+        assert!(matcher.is_match("/repo/apps/web/node_modules/@app/ui/src/Button.tsx", cwd), "Should include listed scoped package");
+        assert!(matcher.is_match("/repo/node_modules/.pnpm/@app+ui@1.0.0/node_modules/@app/ui/src/Button.tsx", cwd), "Should include package from pnpm store");
+        assert!(matcher.is_match("/repo/node_modules/design-system/Button.jsx", cwd), "Should include listed package");
+        assert!(!matcher.is_match("/repo/node_modules/@app/ui/node_modules/lodash/index.js", cwd), "Should use innermost package");
+        assert!(!matcher.is_match("/repo/apps/web/node_modules/@app/ui/src/Button.stories.tsx", cwd), "Should still apply exclude");
+        assert!(matcher.is_match("/repo/apps/web/src/node_modules_docs/Intro.tsx", cwd), "Should only match whole path segments");
+    }
+
+    #[test]
+    fn test_config_errors() {
+        let err = Config::from_json("null").unwrap_err();
+        assert!(matches!(err, ConfigError::Invalid(_)), "Should reject null config");
+
+        let err = Config::from_json(r#"{ "import_path": "mobx-react-lite", "exlude": [] }"#).unwrap_err();
+        assert!(err.to_string().contains("unknown field `exlude`"), "Should reject unknown keys: {}", err);
+
+        let err = Config::from_json(r#"{ "exclude": [] }"#).unwrap_err();
+        assert_eq!(err, ConfigError::Missing { key: "import_path".into() });
+
+        let err = Config::from_json(r#"{ "import_path": "mobx-react-lite", "import_name": "my-observer" }"#).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "import_name"), "Should reject invalid import name");
+
+        let config = Config::from_json(r#"{ "import_path": "mobx-react-lite", "exclude": ["src/**", "src/[a"] }"#).unwrap();
+        let err = FileMatcher::new(&config).unwrap_err();
+        assert!(
+            matches!(err, ConfigError::InvalidValue { ref key, ref value, .. } if key == "exclude[1]" && value == "src/[a"),
+            "Should report the invalid glob: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_config_camel_case() {
+        let config = Config::from_json(
+            r#"{ "importPath": "mobx-react-lite", "importName": "observer", "includeNodeModules": ["@app/*"] }"#,
+        )
+        .unwrap();
+        assert_eq!(config.import_path, "mobx-react-lite");
+        assert_eq!(config.import_name.as_deref(), Some("observer"));
+        assert_eq!(config.include_node_modules, vec!["@app/*".to_string()]);
+
+        let cached = CachedConfig::new(r#"{ "importPath": "mobx-react-lite", "case_insensitive": true }"#).unwrap();
+        assert_eq!(cached.take_warnings().len(), 1, "Should warn about mixed key styles");
+        assert!(cached.take_warnings().is_empty(), "Should only report warnings once");
+
+        let cached = CachedConfig::new(r#"{ "importPath": "mobx-react-lite", "exclude": [] }"#).unwrap();
+        assert!(cached.take_warnings().is_empty(), "Shouldn't warn about consistent keys");
+
+        let cached = CachedConfig::new(
+            r#"{
+                "import_path": "mobx-react-lite",
+                "options": { "forwardRef": true },
+                "wrappers": [{ "import_path": "./tracing", "import_name": "withTracing", "args": [{ "traceName": "x" }] }]
+            }"#,
+        )
+        .unwrap();
+        assert!(cached.take_warnings().is_empty(), "Shouldn't check the keys of values passed on as is");
+
+        let cached = CachedConfig::new(r#"{ "import_path": "mobx-react-lite", "rules": [{ "importPath": "react" }] }"#).unwrap();
+        assert_eq!(cached.take_warnings().len(), 1, "Should check the keys of rules");
+    }
+
+    #[test]
+    fn test_config_preset() {
+        let config = Config::from_json(r#"{ "preset": "mobx-react", "hocOrder": "outer" }"#).unwrap();
+        assert_eq!(config.import_path, "mobx-react");
+        assert_eq!(config.import_name.as_deref(), Some("observer"));
+        assert_eq!(config.import_style, Some(ImportStyle::Named));
+        assert_eq!(config.hoc_order, Some(HocOrder::Outer), "Explicit options should override the preset");
+
+        let config = Config::from_json(r#"{ "preset": "legend-state", "import_path": "./legend" }"#).unwrap();
+        assert_eq!(config.import_path, "./legend", "Explicit options should override the preset");
+
+        let config = Config::from_json(r#"{ "preset": "preact-signals" }"#).unwrap();
+        assert_eq!(config.strategy, Some(Strategy::Hook), "Preact signals should use hook injection");
+        assert_eq!(config.hook_cleanup.as_deref(), Some("f"));
+
+        let err = Config::from_json(r#"{ "import_path": "./tracking", "strategy": "hook", "hook_cleanup": "end()" }"#).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "hook_cleanup"));
+
+        assert!(Config::from_json(r#"{ "preset": "redux" }"#).is_err(), "Should reject unknown presets");
+    }
+
+    #[test]
+    fn test_config_every_preset_validates() {
+        for preset in [Preset::MobxReact, Preset::MobxReactLite, Preset::LegendState, Preset::PreactSignals] {
+            // Matched exhaustively, so new presets have to be listed too
+            let name = match preset {
+                Preset::MobxReact => "mobx-react",
+                Preset::MobxReactLite => "mobx-react-lite",
+                Preset::LegendState => "legend-state",
+                Preset::PreactSignals => "preact-signals",
+            };
+
+            let config = Config::from_json(&format!(r#"{{ "preset": "{}" }}"#, name));
+            assert!(config.is_ok(), "Preset {} should validate: {}", name, config.unwrap_err());
+            assert_eq!(config.unwrap().preset, Some(preset));
+        }
+    }
+
+    #[test]
+    fn test_config_wrappers() {
+        let config = Config::from_json(
+            r#"{ "import_path": "mobx-react-lite", "wrappers": [{ "importPath": "react-error-boundary", "importName": "withErrorBoundary" }] }"#,
+        )
+        .unwrap();
+        assert_eq!(config.wrappers[0].import_name, "withErrorBoundary");
+        assert_eq!(config.wrappers[0].import_style, ImportStyle::Named);
+
+        let err = Config::from_json(
+            r#"{ "import_path": "mobx-react-lite", "wrappers": [{ "import_path": "./hoc", "import_name": "with-hoc" }] }"#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "wrappers[0].import_name"), "Should reject invalid wrapper name: {}", err);
+
+        let err = Config::from_json(r#"{ "import_path": "mobx-react-lite", "wrappers": [{ "import_name": "withHoc" }] }"#).unwrap_err();
+        assert!(err.to_string().contains("missing field `import_path`"), "Should require the wrapper import path: {}", err);
+
+        let err = Config::from_json(
+            r#"{ "import_path": "./tracking", "strategy": "hook", "wrappers": [{ "import_path": "./hoc", "import_name": "withHoc" }] }"#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "wrappers"), "Should reject wrappers with the hook strategy: {}", err);
+
+        let err = Config::from_json(
+            r#"{ "preset": "preact-signals", "rules": [{ "include": ["src/**"], "wrappers": [{ "import_path": "./hoc", "import_name": "withHoc" }] }] }"#,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "rules[0].wrappers"),
+            "Should reject wrappers in a rule with the hook strategy: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_config_options() {
+        let config = Config::from_json(r#"{ "import_path": "./track", "options": { "name": "{componentName}" } }"#).unwrap();
+        assert_eq!(config.options, Some(serde_json::json!({ "name": "{componentName}" })));
+
+        let err = Config::from_json(r#"{ "import_path": "./track", "options": ["{componentName}"] }"#).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "options"), "Should reject options that aren't an object: {}", err);
+    }
+
+    #[test]
+    fn test_config_render_props() {
+        let config = Config::from_json(
+            r#"{ "importPath": "mobx-react-lite", "renderProps": { "attributes": ["renderItem"], "children": true } }"#,
+        )
+        .unwrap();
+        let render_props = config.render_props.unwrap();
+        assert_eq!(render_props.attributes, vec!["renderItem".to_string()]);
+        assert_eq!(render_props.observer_name(), "Observer");
+
+        let err = Config::from_json(
+            r#"{ "import_path": "mobx-react-lite", "render_props": { "attributes": [], "import_name": "my-observer" } }"#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "render_props.import_name"), "Should reject invalid name: {}", err);
+    }
+
+    #[test]
+    fn test_config_actions() {
+        let config = Config::from_json(r#"{ "importPath": "mobx-react-lite", "actions": {} }"#).unwrap();
+        let actions = config.actions.unwrap();
+        assert_eq!(actions.action_path(), "mobx");
+        assert_eq!(actions.action_name(), "action");
+
+        let err = Config::from_json(r#"{ "import_path": "mobx-react-lite", "actions": { "import_path": " " } }"#)
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::Missing { ref key } if key == "actions.import_path"), "Should reject empty path: {}", err);
+    }
+
+    #[test]
+    fn test_config_jsx_import_sources() {
+        let config = Config::from_json(r#"{ "importPath": "mobx-react-lite" }"#).unwrap();
+        assert_eq!(config.jsx_import_sources(), vec!["react", "preact", "@emotion/react"]);
+
+        let config = Config::from_json(r#"{ "importPath": "mobx-react-lite", "jsxImportSources": ["react", "@theme-ui/core"] }"#).unwrap();
+        assert_eq!(config.jsx_import_sources(), vec!["react", "@theme-ui/core"]);
+
+        let err = Config::from_json(r#"{ "import_path": "mobx-react-lite", "jsx_import_sources": [""] }"#).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "jsx_import_sources"), "Should reject empty source: {}", err);
+    }
+
+    #[test]
+    fn test_config_component_names() {
+        let names = Config::default().component_names;
+        assert!(names.is_match("TodoList"));
+        assert!(names.is_match("API_VIEW"), "Should accept any uppercase name by default");
+        assert!(!names.is_match("todoList"));
+
+        let config = Config::from_json(
+            r#"{ "importPath": "mobx-react-lite", "componentNames": { "pascalCase": true, "allow": ["TODO_ITEM"] } }"#,
+        )
+        .unwrap();
+        assert!(!config.component_names.is_match("API_VIEW"), "Should reject SCREAMING_CASE");
+        assert!(config.component_names.is_match("APIView"));
+        assert!(config.component_names.is_match("TODO_ITEM"), "Allowed names should win over the patterns");
+
+        let err = Config::from_json(r#"{ "importPath": "mobx-react-lite", "componentNames": { "include": ["("] } }"#)
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::Invalid(_)), "Should reject invalid regex: {}", err);
+    }
+
+    #[test]
+    fn test_config_skip_components() {
+        let config = Config::from_json(r#"{ "importPath": "mobx-react-lite", "skipComponents": ["App", "*Provider"] }"#);
+        assert!(config.is_ok());
+
+        let err = Config::from_json(r#"{ "importPath": "mobx-react-lite", "skipComponents": ["[Provider"] }"#).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "skip_components[0]"), "Should reject invalid glob: {}", err);
+    }
+
+    #[test]
+    fn test_skip_components_warning() {
+        let config = r#"{ "importPath": "mobx-react-lite", "skipComponents": ["App", "default"] }"#;
+
+        let output = diagnostics(config, "export function App() { return <div />; }");
+        assert!(output.contains("`App` is listed in `skip_components`"), "Should report skipped components: {}", output);
+
+        let output = diagnostics(config, "export default () => <div />;");
+        assert!(output.contains("`default` is listed in `skip_components`"), "Should report skipped default exports: {}", output);
+
+        let output = diagnostics(config, "export const List = () => <ul />;");
+        assert!(output.is_empty(), "Should report nothing else: {}", output);
+    }
+
+    #[test]
+    fn test_map_items_names() {
+        let lifted = lifted_map_items("export const Todos = ({ todos }) => <ul>{todos.map(todo => <li>{todo}</li>)}</ul>;");
+        assert_eq!(lifted, ["TodosItem"]);
+
+        let lifted = lifted_map_items("export default ({ items }) => <ul>{items.map(item => <li>{item}</li>)}</ul>;");
+        assert_eq!(lifted, ["DefaultItem"], "Should name the items of default exports as components");
+
+        let lifted = lifted_map_items(
+            "const TodosItem = 1; export const Todos = ({ todos }) => <ul>{todos.map(todo => <li>{todo}</li>)}</ul>;",
+        );
+        assert_eq!(lifted, ["TodosItem2"], "Should not clash with the names of the module");
+    }
+
+    #[test]
+    fn test_map_items_bail_out() {
+        let bail_outs = [
+            ("this", "items.map(item => <Item item={item} owner={this} />)"),
+            ("arguments", "items.map(item => <Item item={item} args={arguments} />)"),
+            ("assignments", "items.map(item => <Item onClick={() => (selected = item)} />)"),
+            ("updates", "items.map(item => <Item onClick={() => count++} />)"),
+            ("nested functions", "items.map(item => <Item onClick={function () { select(item); }} />)"),
+            ("arrow functions with a block body", "items.map(item => <Item onClick={() => { select(item); }} />)"),
+            ("nested classes", "items.map(item => <Item as={class extends Base {}} />)"),
+            ("block bodies", "items.map(item => { return <Item item={item} />; })"),
+            ("destructured parameters", "items.map(({ id }) => <Item id={id} />)"),
+            ("async callbacks", "items.map(async item => <Item item={item} />)"),
+            ("callbacks not returning JSX", "items.map(item => item.visible && <Item item={item} />)"),
+            ("a parameter named like a reserved prop", "items.map(key => <Item id={key} />)"),
+            ("a captured local named like a reserved prop", "items.map(item => <Item item={item} id={ref} />)"),
+        ];
+
+        let component = |map: &str| {
+            format!("export const List = ({{ items, ref }}) => {{ let selected, count; return <ul>{{{}}}</ul>; }};", map)
+        };
+        assert_eq!(lifted_map_items(&component("items.map(item => <Item item={item} />)")), ["ListItem"]);
+
+        for (reason, map) in bail_outs {
+            let code = component(map);
+            assert!(lifted_map_items(&code).is_empty(), "Should not lift callbacks using {}: {}", reason, code);
+        }
+    }
+
+    #[test]
+    fn test_memo_comparator_warning() {
+        let code = r#"
+            import { memo } from "react";
+            export const Header = memo(({ title }) => <th>{title}</th>, (prev, next) => prev.title === next.title);
+        "#;
+
+        let output = diagnostics(r#"{ "importPath": "mobx-react-lite" }"#, code);
+        assert!(output.contains("the `memo` comparator of `Header` is dropped"), "Should report the dropped comparator: {}", output);
+
+        let output = diagnostics(r#"{ "importPath": "./observer", "observerComparator": true }"#, code);
+        assert!(output.is_empty(), "Should keep the comparator silently: {}", output);
+    }
+
+    #[test]
+    fn test_config_lazy_factories() {
+        let config = Config::from_json(r#"{ "importPath": "mobx-react-lite" }"#).unwrap();
+        assert_eq!(config.lazy_factories(), vec!["lazy", "loadable"]);
+
+        let err = Config::from_json(r#"{ "importPath": "mobx-react-lite", "lazyFactories": ["React.lazy"] }"#).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "lazy_factories"), "Should reject member expressions: {}", err);
+    }
+
+    #[test]
+    fn test_config_rules() {
+        let cached = CachedConfig::new(
+            r#"{
+                "import_path": "mobx-react-lite",
+                "exclude": ["**/*.stories.tsx"],
+                "rules": [
+                    { "include": ["src/legacy/**"], "preset": "mobx-react" },
+                    { "include": ["src/ui/**"], "importPath": "react", "importName": "memo" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let cwd = Some("/repo");
+
+        let config = cached.config_for("/repo/src/legacy/App.tsx", cwd).unwrap();
+        assert_eq!(config.import_path, "mobx-react", "Should use the options of the matching rule");
+        assert_eq!(config.hoc_order, Some(HocOrder::Inner), "Should apply the preset of the rule");
+
+        let config = cached.config_for("/repo/src/ui/Button.tsx", cwd).unwrap();
+        assert_eq!(config.import_name.as_deref(), Some("memo"));
+
+        let config = cached.config_for("/repo/src/features/Todo.tsx", cwd).unwrap();
+        assert_eq!(config.import_path, "mobx-react-lite", "Should fall back to the top-level options");
+
+        assert!(cached.config_for("/repo/src/ui/Button.stories.tsx", cwd).is_none(), "Top-level exclude should still apply");
+
+        let cached = CachedConfig::new(r#"{ "rules": [{ "include": ["src/**"], "import_path": "mobx-react-lite" }] }"#).unwrap();
+        assert!(cached.config_for("/repo/src/App.tsx", cwd).is_some());
+        assert!(cached.config_for("/repo/lib/App.tsx", cwd).is_none(), "Shouldn't transform files matching no rule without import_path");
+
+        let err = Config::from_json(r#"{ "rules": [{ "include": ["src/**"] }] }"#).unwrap_err();
+        assert_eq!(err, ConfigError::Missing { key: "rules[0].import_path".into() });
+
+        let err = CachedConfig::new(r#"{ "import_path": "mobx-react-lite", "rules": [{ "include": ["src/[a"] }] }"#).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "rules[0].include[0]"), "Should report the rule: {}", err);
+    }
+
+    #[test]
+    fn test_resolve_import_path() {
+        let cwd = Some("/repo");
+
+        assert_eq!(
+            resolve_import_path("/src/lib/observer", "/repo/src/features/todo/Todo.tsx", cwd),
+            "../../lib/observer",
+            "Should resolve from nested directories"
+        );
+        assert_eq!(
+            resolve_import_path("/src/lib/observer", "/repo/src/lib/Button.tsx", cwd),
+            "./observer",
+            "Should resolve from the same directory"
+        );
+        assert_eq!(
+            resolve_import_path("/src/lib/observer", "/repo/src/ui/Button.tsx", cwd),
+            "../lib/observer",
+            "Should resolve from sibling directories"
+        );
+        assert_eq!(
+            resolve_import_path("/src/lib/observer", "/repo/App.tsx", cwd),
+            "./src/lib/observer",
+            "Should resolve from the root directory"
+        );
+        assert_eq!(
+            resolve_import_path("/src/lib/observer", "src/ui/Button.tsx", cwd),
+            "../lib/observer",
+            "Should resolve file names relative to the cwd"
+        );
+        assert_eq!(
+            resolve_import_path("/src/lib/observer", "C:\\repo\\src\\ui\\Button.tsx", Some("C:\\repo")),
+            "../lib/observer",
+            "Should handle Windows paths"
+        );
+        assert_eq!(resolve_import_path("mobx-react-lite", "/repo/src/App.tsx", cwd), "mobx-react-lite");
+        assert_eq!(resolve_import_path("/src/lib/observer", "/repo/src/App.tsx", None), "/src/lib/observer");
+    }
 }
@@ -1,7 +1,11 @@
-use std::path::PathBuf;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 
-use swc_common::Mark;
+use swc_common::{comments::Comments, Mark};
+use swc_ecma_ast::{Pass, Program};
 use swc_ecma_parser::{EsSyntax, Syntax};
 use swc_ecma_transforms_base::resolver;
 use swc_ecma_transforms_testing::{test_fixture, FixtureTestConfig};
@@ -13,30 +17,63 @@ fn syntax() -> Syntax {
     })
 }
 
+// Fixtures can override the default config with a `config.json` next to the input
+fn config(input: &Path) -> wrap_components_with_observer::Config {
+    let config_path = input.parent().unwrap().join("config.json");
+
+    match fs::read_to_string(config_path) {
+        Ok(raw) => wrap_components_with_observer::Config::from_json(&raw).unwrap(),
+        Err(_) => wrap_components_with_observer::Config {
+            import_path: "bonsify".into(),
+            import_name: None,
+            exclude: vec!["node_modules".into()],
+            ..Default::default()
+        },
+    }
+}
+
+// Like the plugin, applies the header comment to the config of both passes
+// and leaves files targeting another runtime alone
+struct Plugin<C: Comments + Clone> {
+    config: wrap_components_with_observer::Config,
+    comments: C,
+}
+
+impl<C: Comments + Clone> Pass for Plugin<C> {
+    fn process(&mut self, program: &mut Program) {
+        let config = wrap_components_with_observer::header_config(program, &self.config, &self.comments);
+        if !wrap_components_with_observer::targets_react(program, &config, &self.comments) {
+            return;
+        }
+
+        wrap_components_with_observer::action_transform(config.clone(), self.comments.clone()).process(program);
+        wrap_components_with_observer::observer_transform(config, "input.js", self.comments.clone()).process(program);
+    }
+}
+
 #[testing::fixture("tests/fixture/**/input.js")]
 fn fixture(input: PathBuf) {
     let output = input.parent().unwrap().join("output.js");
+    let config = config(&input);
     test_fixture(
         syntax(),
-        &|_tr| {
+        &|tr| {
             let unresolved_mark = Mark::new();
             let top_level_mark = Mark::new();
 
             (
                 resolver(unresolved_mark, top_level_mark, false),
-                wrap_components_with_observer::observer_transform(
-                    wrap_components_with_observer::Config {
-                        import_path: "bonsify".into(),
-                        import_name: None,
-                        exclude: vec!["node_modules".into()],
-                    }
-                ),
+                Plugin {
+                    config: config.clone(),
+                    comments: tr.comments.clone(),
+                },
             )
         },
         &input,
         &output,
         FixtureTestConfig {
+            allow_error: true,
             ..Default::default()
         },
     );
-}
\ No newline at end of file
+}
@@ -0,0 +1,21 @@
+import { action } from "mobx";
+import { store } from "./store";
+
+const handleReset = () => store.reset();
+
+export function Counter() {
+  return (
+    <div>
+      <button onClick={() => store.count++}>Increment</button>
+      <button onClick={function () { store.count--; }}>Decrement</button>
+      <button onClick={handleReset}>Reset</button>
+      <button onClick={action(() => store.count = 0)}>Clear</button>
+      <input onChange={(event) => store.label = event.target.value} value={store.label} />
+      <Child render={() => store.count} />
+    </div>
+  );
+}
+
+function helper() {
+  return <button onClick={() => store.count++} />;
+}
@@ -0,0 +1,19 @@
+import { observer } from "mobx-react-lite";
+import { action } from "mobx";
+import { store } from "./store";
+const handleReset = ()=>store.reset();
+export const Counter = observer(function Counter() {
+    return <div>
+      <button onClick={action(()=>store.count++)}>Increment</button>
+      <button onClick={action(function() {
+        store.count--;
+    })}>Decrement</button>
+      <button onClick={handleReset}>Reset</button>
+      <button onClick={action(()=>store.count = 0)}>Clear</button>
+      <input onChange={action((event)=>store.label = event.target.value)} value={store.label}/>
+      <Child render={()=>store.count}/>
+    </div>;
+});
+function helper() {
+    return <button onClick={()=>store.count++}/>;
+}
@@ -0,0 +1,16 @@
+import { store } from "./store";
+
+function Private() {
+  return <button onClick={() => store.count++}>Private</button>;
+}
+
+export const Static = () => <button onClick={() => console.log("clicked")}>Static</button>;
+
+export function Counter() {
+  return (
+    <div>
+      <Private />
+      <button onClick={() => store.count++}>Increment</button>
+    </div>
+  );
+}
@@ -0,0 +1,13 @@
+import { observer } from "mobx-react-lite";
+import { action } from "mobx";
+import { store } from "./store";
+function Private() {
+    return <button onClick={()=>store.count++}>Private</button>;
+}
+export const Static = ()=><button onClick={()=>console.log("clicked")}>Static</button>;
+export const Counter = observer(function Counter() {
+    return <div>
+      <Private/>
+      <button onClick={action(()=>store.count++)}>Increment</button>
+    </div>;
+});
@@ -0,0 +1,3 @@
+import { store } from "./store";
+
+export const Toggle = () => <button onClick={() => store.toggle()}>Toggle</button>;
@@ -0,0 +1,4 @@
+import { observer } from "mobx-react-lite";
+import { runInAction } from "./actions";
+import { store } from "./store";
+export const Toggle = observer(()=><button onClick={runInAction(()=>store.toggle())}>Toggle</button>);
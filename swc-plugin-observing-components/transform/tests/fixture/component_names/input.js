@@ -0,0 +1,13 @@
+export const TodoList = () => <ul />;
+
+export const API_RESPONSE_VIEW = () => <pre />;
+
+export const TODO_ITEM = () => <li />;
+
+export const $Button = () => <button />;
+
+export const LegacyPanel = () => <div />;
+
+export function Preview() {
+  return <section />;
+}
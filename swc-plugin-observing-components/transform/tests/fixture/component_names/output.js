@@ -0,0 +1,9 @@
+import { observer } from "mobx-react-lite";
+export const TodoList = observer(()=><ul/>);
+export const API_RESPONSE_VIEW = ()=><pre/>;
+export const TODO_ITEM = observer(()=><li/>);
+export const $Button = observer(()=><button/>);
+export const LegacyPanel = ()=><div/>;
+export function Preview() {
+    return <section/>;
+}
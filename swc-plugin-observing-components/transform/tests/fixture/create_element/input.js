@@ -0,0 +1,12 @@
+import React, { createElement } from "react";
+
+export const Title = ({ title }) => React.createElement("h1", null, title);
+
+export function Page({ title }) {
+  return createElement("main", null, createElement(Title, { title }));
+}
+
+export function Portal() {
+  const element = document.createElement("div");
+  return element;
+}
@@ -0,0 +1,12 @@
+import { observer } from "bonsify";
+import React, { createElement } from "react";
+export const Title = observer(({ title })=>React.createElement("h1", null, title));
+export const Page = observer(function Page({ title }) {
+    return createElement("main", null, createElement(Title, {
+        title
+    }));
+});
+export function Portal() {
+    const element = document.createElement("div");
+    return element;
+}
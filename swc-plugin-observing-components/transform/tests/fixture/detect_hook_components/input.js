@@ -0,0 +1,23 @@
+import { useUser } from "./user";
+import { todoStore } from "./stores";
+
+export function Layout({ children }) {
+  const user = useUser();
+  return user ? children : null;
+}
+
+export const Content = () => renderContent(todoStore.todos);
+
+export function useTodos() {
+  return useUser().todos;
+}
+
+function formatUser() {
+  return useUser().name;
+}
+
+export const Config = { title: "Todos" };
+
+export function Empty() {
+  return null;
+}
@@ -0,0 +1,20 @@
+import { observer } from "mobx-react-lite";
+import { useUser } from "./user";
+import { todoStore } from "./stores";
+export const Layout = observer(function Layout({ children }) {
+    const user = useUser();
+    return user ? children : null;
+});
+export const Content = observer(()=>renderContent(todoStore.todos));
+export function useTodos() {
+    return useUser().todos;
+}
+function formatUser() {
+    return useUser().name;
+}
+export const Config = {
+    title: "Todos"
+};
+export function Empty() {
+    return null;
+}
@@ -0,0 +1,11 @@
+import { useUser } from "./user";
+
+export const Static = () => {
+  const load = () => useUser();
+  return renderStatic(load);
+};
+
+export default function Layout({ children }) {
+  useUser();
+  return children;
+}
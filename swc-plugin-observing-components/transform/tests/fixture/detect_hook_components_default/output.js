@@ -0,0 +1,10 @@
+import { observer } from "mobx-react-lite";
+import { useUser } from "./user";
+export const Static = ()=>{
+    const load = ()=>useUser();
+    return renderStatic(load);
+};
+export default observer(function Layout({ children }) {
+    useUser();
+    return children;
+});
@@ -0,0 +1,5 @@
+import { useUser } from "./user";
+
+export default function useTodos() {
+  return useUser().todos;
+}
@@ -0,0 +1,4 @@
+import { useUser } from "./user";
+export default function useTodos() {
+    return useUser().todos;
+}
@@ -0,0 +1,18 @@
+function Row({ todo }) {
+  return <li>{todo.title}</li>;
+}
+
+const Header = () => <h1>Todos</h1>;
+
+const List = ({ todos }) => <ul>{todos.map((todo) => <li key={todo.id}>{todo.title}</li>)}</ul>;
+
+const Private = ({ items }) => <ol>{items.map((item) => <li key={item}>{item}</li>)}</ol>;
+
+export const Footer = () => <footer />;
+
+function Page() {
+  return <main />;
+}
+
+export { List as TodoList, Row };
+export default Page;
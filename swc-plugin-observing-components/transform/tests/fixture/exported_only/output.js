@@ -0,0 +1,16 @@
+import { observer } from "mobx-react-lite";
+const Row = observer(function Row({ todo }) {
+    return <li>{todo.title}</li>;
+});
+const Header = ()=><h1>Todos</h1>;
+const ListItem = observer(function ListItem({ todo }) {
+    return <li>{todo.title}</li>;
+});
+const List = observer(({ todos })=><ul>{todos.map((todo)=><ListItem key={todo.id} todo={todo}/>)}</ul>);
+const Private = ({ items })=><ol>{items.map((item)=><li key={item}>{item}</li>)}</ol>;
+export const Footer = observer(()=><footer/>);
+const Page = observer(function Page() {
+    return <main/>;
+});
+export { List as TodoList, Row };
+export default Page;
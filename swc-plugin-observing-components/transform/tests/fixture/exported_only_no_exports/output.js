@@ -0,0 +1,6 @@
+import { createRoot } from "react-dom/client";
+const Header = ()=><h1>Todos</h1>;
+function App() {
+    return <Header/>;
+}
+createRoot(document.getElementById("root")).render(<App/>);
@@ -0,0 +1,34 @@
+import { observer } from "mobx-react-lite";
+
+export const Todos = ({ todos, prefix }) => {
+  const remove = (todo) => todos.remove(todo);
+
+  return (
+    <ul>
+      {todos.map((todo, index) => (
+        <li key={todo.id} onClick={() => remove(todo)}>
+          {prefix} {index}: {todo.title}
+        </li>
+      ))}
+    </ul>
+  );
+};
+
+export function Table({ rows }) {
+  return (
+    <table>
+      {rows.map((row) => <tr key={row.id}>{row.cells.map((cell) => <td>{cell}</td>)}</tr>)}
+    </table>
+  );
+}
+
+// Not lifted: uses `this`, a block body and destructured parameters
+export const Skipped = ({ items }) => (
+  <div>
+    {items.map((item) => <Item item={item} owner={this} />)}
+    {items.map((item) => {
+      return <Item item={item} />;
+    })}
+    {items.map(({ id }) => <Item id={id} />)}
+  </div>
+);
@@ -0,0 +1,31 @@
+import { observer } from "mobx-react-lite";
+const TodosItem = observer(function TodosItem({ todo, index, remove, prefix }) {
+    return <li onClick={()=>remove(todo)}>
+          {prefix} {index}: {todo.title}
+        </li>;
+});
+export const Todos = observer(({ todos, prefix })=>{
+    const remove = (todo)=>todos.remove(todo);
+    return <ul>
+      {todos.map((todo, index)=><TodosItem key={todo.id} todo={todo} index={index} remove={remove} prefix={prefix}/>)}
+    </ul>;
+});
+const TableItem = observer(function TableItem({ cell }) {
+    return <td>{cell}</td>;
+});
+const TableItem2 = observer(function TableItem2({ row }) {
+    return <tr>{row.cells.map((cell)=><TableItem cell={cell}/>)}</tr>;
+});
+export const Table = observer(function Table({ rows }) {
+    return <table>
+      {rows.map((row)=><TableItem2 key={row.id} row={row}/>)}
+    </table>;
+});
+// Not lifted: uses `this`, a block body and destructured parameters
+export const Skipped = observer(({ items })=><div>
+    {items.map((item)=><Item item={item} owner={this}/>)}
+    {items.map((item)=>{
+        return <Item item={item}/>;
+    })}
+    {items.map(({ id })=><Item id={id}/>)}
+  </div>);
@@ -0,0 +1,5 @@
+import { observer } from "mobx-react-lite";
+const DefaultItem = observer(function DefaultItem({ item }) {
+    return <li>{item.name}</li>;
+});
+export default observer(({ items })=><ul>{items.map((item)=><DefaultItem key={item.id} item={item}/>)}</ul>);
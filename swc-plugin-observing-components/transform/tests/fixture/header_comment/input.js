@@ -0,0 +1,2 @@
+/** @observing-components importPath=./local-observer importName=track */
+export const Home = () => <div />;
@@ -0,0 +1,2 @@
+/** @observing-components importPath=./local-observer importName=track */ import { track } from "./local-observer";
+export const Home = track(()=><div/>);
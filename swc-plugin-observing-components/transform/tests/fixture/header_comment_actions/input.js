@@ -0,0 +1,4 @@
+/** @observing-components importPath=./local-observer importName=track */
+import { store } from "./store";
+
+export const Counter = () => <button onClick={() => store.count++}>{store.count}</button>;
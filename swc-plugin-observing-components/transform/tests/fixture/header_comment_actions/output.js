@@ -0,0 +1,4 @@
+/** @observing-components importPath=./local-observer importName=track */ import { track } from "./local-observer";
+import { action } from "mobx";
+import { store } from "./store";
+export const Counter = track(()=><button onClick={action(()=>store.count++)}>{store.count}</button>);
@@ -0,0 +1,2 @@
+/** @observing-components importPath=./local-observer importNmae=track */
+export const Home = () => <div />;
@@ -0,0 +1,2 @@
+/** @observing-components importPath=./local-observer importNmae=track */ import { observer } from "bonsify";
+export const Home = observer(()=><div/>);
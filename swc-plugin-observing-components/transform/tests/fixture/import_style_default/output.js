@@ -0,0 +1,4 @@
+import track from "./observer";
+export const Home = track(function Home() {
+    return <div/>;
+});
@@ -0,0 +1,5 @@
+import t from "./observer";
+
+export function Home() {
+  return <div onClick={() => t("home")} />;
+}
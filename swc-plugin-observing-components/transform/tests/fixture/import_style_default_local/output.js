@@ -0,0 +1,5 @@
+import track from "./observer";
+import t from "./observer";
+export const Home = track(function Home() {
+    return <div onClick={()=>t("home")}/>;
+});
@@ -0,0 +1,2 @@
+/** @jsxImportSource @emotion/react */
+export const Title = ({ title }) => <h1 css={{ color: "hotpink" }}>{title}</h1>;
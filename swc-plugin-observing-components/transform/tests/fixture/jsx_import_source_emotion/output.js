@@ -0,0 +1,4 @@
+/** @jsxImportSource @emotion/react */ import { observer } from "bonsify";
+export const Title = observer(({ title })=><h1 css={{
+        color: "hotpink"
+    }}>{title}</h1>);
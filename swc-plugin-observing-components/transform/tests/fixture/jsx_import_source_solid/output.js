@@ -0,0 +1,5 @@
+/** @jsxImportSource solid-js */ import { createSignal } from "solid-js";
+export function Counter() {
+    const [count, setCount] = createSignal(0);
+    return <button onClick={()=>setCount(count() + 1)}>{count()}</button>;
+}
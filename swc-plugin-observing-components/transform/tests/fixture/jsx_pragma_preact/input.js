@@ -0,0 +1,12 @@
+/** @jsx h */
+import { h } from "preact";
+
+export const Title = ({ title }) => h("h1", null, title);
+
+export function Page({ title }) {
+  return h("main", null, h(Title, { title }));
+}
+
+export function Helper() {
+  return helper("main");
+}
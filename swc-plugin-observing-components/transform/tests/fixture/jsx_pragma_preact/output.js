@@ -0,0 +1,11 @@
+/** @jsx h */ import { observer } from "bonsify";
+import { h } from "preact";
+export const Title = observer(({ title })=>h("h1", null, title));
+export const Page = observer(function Page({ title }) {
+    return h("main", null, h(Title, {
+        title
+    }));
+});
+export function Helper() {
+    return helper("main");
+}
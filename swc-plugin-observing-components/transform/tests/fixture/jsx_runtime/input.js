@@ -0,0 +1,13 @@
+import { jsx as _jsx, jsxs as _jsxs } from "react/jsx-runtime";
+
+export const Title = ({ title }) => _jsx("h1", { children: title });
+
+export function Page({ title, body }) {
+  return _jsxs("main", {
+    children: [_jsx(Title, { title }), _jsx("p", { children: body })],
+  });
+}
+
+export function formatTitle(title) {
+  return title.toUpperCase();
+}
@@ -0,0 +1,20 @@
+import { observer } from "bonsify";
+import { jsx as _jsx, jsxs as _jsxs } from "react/jsx-runtime";
+export const Title = observer(({ title })=>_jsx("h1", {
+        children: title
+    }));
+export const Page = observer(function Page({ title, body }) {
+    return _jsxs("main", {
+        children: [
+            _jsx(Title, {
+                title
+            }),
+            _jsx("p", {
+                children: body
+            })
+        ]
+    });
+});
+export function formatTitle(title) {
+    return title.toUpperCase();
+}
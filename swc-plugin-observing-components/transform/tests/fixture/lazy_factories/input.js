@@ -0,0 +1,20 @@
+import React, { lazy } from "react";
+import loadable from "@loadable/component";
+
+export const Settings = lazy(() => import("./Settings"));
+
+export const Preview = lazy(() => Promise.resolve({ default: () => <div>Preview</div> }));
+
+export const Chart = loadable(() => import("./Chart"), { fallback: <Spinner /> });
+
+export const Profile = React.lazy(() => Promise.resolve({ default: () => <div>Profile</div> }));
+
+export const Editor = lazy(async () => {
+  const m = await import("./Editor");
+  return { default: () => <m.Editor /> };
+});
+
+export default lazy(async () => {
+  const m = await import("./Home");
+  return () => <m.Home />;
+});
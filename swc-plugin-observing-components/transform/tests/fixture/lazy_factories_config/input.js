@@ -0,0 +1,10 @@
+import lazyWithPreload from "react-lazy-with-preload";
+
+export const Settings = lazyWithPreload(() => Promise.resolve({ default: () => <div>Settings</div> }));
+
+export const Page = () => <main />;
+
+export const Editor = lazyWithPreload(async () => {
+  const m = await import("./Editor");
+  return () => <m.Editor />;
+});
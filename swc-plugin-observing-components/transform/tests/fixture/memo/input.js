@@ -0,0 +1,14 @@
+import React, { memo } from "react";
+import { memo as customMemo } from "./memo";
+
+export const Row = memo(() => <tr />);
+
+export const Cell = React.memo(function Cell({ value }) {
+  return <td>{value}</td>;
+});
+
+export const Header = memo(({ title }) => <th>{title}</th>, (prev, next) => prev.title === next.title);
+
+export const Footer = customMemo(() => <tfoot />);
+
+export default memo(() => <table />);
@@ -0,0 +1,10 @@
+import { observer } from "mobx-react-lite";
+import React, { memo } from "react";
+import { memo as customMemo } from "./memo";
+export const Row = observer(()=><tr/>);
+export const Cell = observer(function Cell({ value }) {
+    return <td>{value}</td>;
+});
+export const Header = observer(({ title })=><th>{title}</th>);
+export const Footer = observer(customMemo(()=><tfoot/>));
+export default observer(()=><table/>);
@@ -0,0 +1,3 @@
+import { memo } from "react";
+
+export const Header = memo(({ title }) => <th>{title}</th>, (prev, next) => prev.title === next.title);
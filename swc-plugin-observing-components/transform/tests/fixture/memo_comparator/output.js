@@ -0,0 +1,3 @@
+import { observer } from "./observer";
+import { memo } from "react";
+export const Header = observer(({ title })=><th>{title}</th>, (prev, next)=>prev.title === next.title);
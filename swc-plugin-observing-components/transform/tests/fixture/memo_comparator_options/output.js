@@ -0,0 +1,5 @@
+import { observer } from "./observer";
+import { memo } from "react";
+export const Header = observer(({ title })=><th>{title}</th>, (prev, next)=>prev.title === next.title, {
+    forwardRef: true
+});
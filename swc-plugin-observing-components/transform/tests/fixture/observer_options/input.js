@@ -0,0 +1,7 @@
+export const Home = () => <div />;
+
+function Profile() {
+  return <div />;
+}
+
+export default () => <Profile />;
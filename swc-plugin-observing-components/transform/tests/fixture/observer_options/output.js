@@ -0,0 +1,18 @@
+import { track } from "./track";
+export const Home = track(()=><div/>, {
+    name: "Home",
+    scope: "input.js:Home",
+    forwardRef: true
+});
+const Profile = track(function Profile() {
+    return <div/>;
+}, {
+    name: "Profile",
+    scope: "input.js:Profile",
+    forwardRef: true
+});
+export default track(()=><Profile/>, {
+    name: "default",
+    scope: "input.js:default",
+    forwardRef: true
+});
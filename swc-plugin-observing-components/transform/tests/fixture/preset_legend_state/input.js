@@ -0,0 +1,7 @@
+import { reactive } from "@legendapp/state/react";
+
+export const Counter = reactive(() => <div />);
+
+export const Home = withRouter(() => <div />);
+
+export const Input = forwardRef((props, ref) => <input ref={ref} />);
@@ -0,0 +1,5 @@
+import { observer } from "@legendapp/state/react";
+import { reactive } from "@legendapp/state/react";
+export const Counter = reactive(()=><div/>);
+export const Home = observer(withRouter(()=><div/>));
+export const Input = forwardRef((props, ref)=><input ref={ref}/>);
@@ -0,0 +1,9 @@
+import { observer } from "mobx-react-lite";
+
+export const Home = withRouter(() => {
+  return <div />;
+});
+
+export const Input = React.forwardRef((props, ref) => <input ref={ref} />);
+
+export const Done = withRouter(observer(() => <div />));
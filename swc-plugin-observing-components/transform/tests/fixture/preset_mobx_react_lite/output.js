@@ -0,0 +1,6 @@
+import { observer } from "mobx-react-lite";
+export const Home = withRouter(observer(()=>{
+    return <div/>;
+}));
+export const Input = observer(React.forwardRef((props, ref)=><input ref={ref}/>));
+export const Done = withRouter(observer(()=><div/>));
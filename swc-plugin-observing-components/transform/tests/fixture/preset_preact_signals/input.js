@@ -0,0 +1,3 @@
+export function Counter() {
+  return <div>{count.value}</div>;
+}
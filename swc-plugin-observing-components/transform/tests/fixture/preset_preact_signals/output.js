@@ -0,0 +1,9 @@
+import { useSignals } from "@preact/signals-react/runtime";
+export function Counter() {
+    const _t = useSignals();
+    try {
+        return <div>{count.value}</div>;
+    } finally{
+        _t.f();
+    }
+}
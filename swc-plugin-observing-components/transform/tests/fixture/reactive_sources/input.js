@@ -0,0 +1,22 @@
+import { todoStore } from "./stores";
+import { useSession } from "@app/state";
+import { formatDate } from "./format";
+
+export const Count = () => <span>{todoStore.todos.length}</span>;
+
+export function User() {
+  const session = useSession();
+  return <span>{session.user.name}</span>;
+}
+
+export const Title = ({ title }) => <h1>{title}</h1>;
+
+export const Divider = () => <hr />;
+
+export function Today() {
+  return <time>{formatDate(new Date())}</time>;
+}
+
+export default function Logo({}) {
+  return <img src="/logo.svg" />;
+}
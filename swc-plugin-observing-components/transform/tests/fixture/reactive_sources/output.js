@@ -0,0 +1,17 @@
+import { observer } from "mobx-react-lite";
+import { todoStore } from "./stores";
+import { useSession } from "@app/state";
+import { formatDate } from "./format";
+export const Count = observer(()=><span>{todoStore.todos.length}</span>);
+export const User = observer(function User() {
+    const session = useSession();
+    return <span>{session.user.name}</span>;
+});
+export const Title = observer(({ title })=><h1>{title}</h1>);
+export const Divider = ()=><hr/>;
+export function Today() {
+    return <time>{formatDate(new Date())}</time>;
+}
+export default function Logo({}) {
+    return <img src="/logo.svg"/>;
+}
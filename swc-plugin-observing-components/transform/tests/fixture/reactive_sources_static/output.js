@@ -0,0 +1,8 @@
+import { formatDate } from "./format";
+export const Divider = ()=><hr/>;
+export function Today() {
+    return <time>{formatDate(new Date())}</time>;
+}
+export default function Footer() {
+    return <footer>© 2024</footer>;
+}
@@ -0,0 +1,20 @@
+import { Observer } from "mobx-react-lite";
+
+export const Todos = ({ todos }) => (
+  <List
+    items={todos}
+    renderItem={(todo) => <Todo todo={todo} />}
+    renderEmpty={() => <Empty />}
+  />
+);
+
+export const User = () => (
+  <Query>
+    {function (user) {
+      const name = user.name;
+      return <div>{name}</div>;
+    }}
+  </Query>
+);
+
+export const Counter = () => <Observer>{() => <div>{count.value}</div>}</Observer>;
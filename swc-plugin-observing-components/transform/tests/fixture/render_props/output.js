@@ -0,0 +1,12 @@
+import { observer } from "mobx-react-lite";
+import { Observer } from "mobx-react-lite";
+export const Todos = observer(({ todos })=><List items={todos} renderItem={(todo)=><Observer>{()=><Todo todo={todo}/>}</Observer>} renderEmpty={()=><Empty/>}/>);
+export const User = observer(()=><Query>
+    {function(user) {
+        return <Observer>{()=>{
+            const name = user.name;
+            return <div>{name}</div>;
+        }}</Observer>;
+    }}
+  </Query>);
+export const Counter = observer(()=><Observer>{()=><div>{count.value}</div>}</Observer>);
@@ -0,0 +1 @@
+export const todos = <List renderItem={(todo) => <Todo todo={todo} />} />;
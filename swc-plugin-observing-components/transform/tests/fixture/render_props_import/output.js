@@ -0,0 +1,2 @@
+import { Observer } from "mobx-react-lite";
+export const todos = <List renderItem={(todo)=><Observer>{()=><Todo todo={todo}/>}</Observer>}/>;
@@ -0,0 +1,7 @@
+export function ErrorBoundary({ children }) {
+  return <Boundary>{children}</Boundary>;
+}
+
+export const StoreProvider = ({ children }) => <Context.Provider value={store}>{children}</Context.Provider>;
+
+export const TodoList = () => <ul />;
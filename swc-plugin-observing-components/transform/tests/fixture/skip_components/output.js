@@ -0,0 +1,6 @@
+import { observer } from "mobx-react-lite";
+export function ErrorBoundary({ children }) {
+    return <Boundary>{children}</Boundary>;
+}
+export const StoreProvider = ({ children })=><Context.Provider value={store}>{children}</Context.Provider>;
+export const TodoList = observer(()=><ul/>);
@@ -0,0 +1,5 @@
+export const ThemeProvider = ({ children }) => <div className="theme">{children}</div>;
+
+export default function App() {
+  return <ThemeProvider />;
+}
@@ -0,0 +1,4 @@
+export const ThemeProvider = ({ children })=><div className="theme">{children}</div>;
+export default function App() {
+    return <ThemeProvider/>;
+}
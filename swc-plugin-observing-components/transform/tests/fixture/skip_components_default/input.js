@@ -0,0 +1,3 @@
+export const TodoList = () => <ul />;
+
+export default () => <TodoList />;
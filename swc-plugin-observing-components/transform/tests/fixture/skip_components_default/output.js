@@ -0,0 +1,3 @@
+import { observer } from "mobx-react-lite";
+export const TodoList = observer(()=><ul/>);
+export default (()=><TodoList/>);
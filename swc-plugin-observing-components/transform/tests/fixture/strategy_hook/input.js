@@ -0,0 +1,12 @@
+export const Home = () => <div />;
+
+export function Profile({ user }) {
+  const name = user.name;
+  return <div>{name}</div>;
+}
+
+const Input = forwardRef((props, ref) => <input ref={ref} />);
+
+export default function App() {
+  return <Home />;
+}
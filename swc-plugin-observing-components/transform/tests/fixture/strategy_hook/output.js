@@ -0,0 +1,34 @@
+import { useTracking } from "./tracking";
+export const Home = ()=>{
+    const _t = useTracking();
+    try {
+        return <div/>;
+    } finally{
+        _t.end();
+    }
+};
+export function Profile({ user }) {
+    const _t = useTracking();
+    try {
+        const name = user.name;
+        return <div>{name}</div>;
+    } finally{
+        _t.end();
+    }
+}
+const Input = forwardRef((props, ref)=>{
+    const _t = useTracking();
+    try {
+        return <input ref={ref}/>;
+    } finally{
+        _t.end();
+    }
+});
+export default function App() {
+    const _t = useTracking();
+    try {
+        return <Home/>;
+    } finally{
+        _t.end();
+    }
+}
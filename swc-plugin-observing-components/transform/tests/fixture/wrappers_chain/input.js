@@ -0,0 +1,12 @@
+import { observer } from "mobx-react-lite";
+import { withErrorBoundary } from "react-error-boundary";
+
+export const Home = () => <div />;
+
+export const Profile = observer(() => <div />);
+
+export const Settings = withErrorBoundary(observer(() => <div />), { fallback: null });
+
+export default function App() {
+  return <Home />;
+}
@@ -0,0 +1,5 @@
+import { observer } from "mobx-react-lite";
+import { withErrorBoundary } from "react-error-boundary";
+export const Home = withErrorBoundary(observer(()=><div/>), {
+    fallback: null
+});
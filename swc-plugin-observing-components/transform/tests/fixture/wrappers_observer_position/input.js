@@ -0,0 +1,5 @@
+import withTracing from "./tracing";
+
+export const Home = () => <div />;
+
+export const Profile = withTracing(() => <div />);
@@ -0,0 +1,4 @@
+import { observer } from "mobx-react-lite";
+import withTracing from "./tracing";
+export const Home = observer(withTracing(()=><div/>));
+export const Profile = observer(withTracing(()=><div/>));
@@ -0,0 +1,8 @@
+import { observer } from "mobx-react-lite";
+import { withErrorBoundary } from "react-error-boundary";
+
+export const Home = withErrorBoundary(() => <div />, { fallback: null });
+
+export const Profile = withErrorBoundary(observer(() => <div />), { fallback: null });
+
+export default withErrorBoundary(() => <div />, { fallback: <p>Oops</p> });
@@ -0,0 +1,11 @@
+import { observer } from "mobx-react-lite";
+import { withErrorBoundary } from "react-error-boundary";
+export const Home = withErrorBoundary(observer(()=><div/>), {
+    fallback: null
+});
+export const Profile = withErrorBoundary(observer(()=><div/>), {
+    fallback: null
+});
+export default withErrorBoundary(observer(()=><div/>), {
+    fallback: <p>Oops</p>
+});